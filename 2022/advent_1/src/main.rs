@@ -41,32 +41,23 @@ In case the Elves get hungry and need extra snacks, they need to know which Elf
 Find the Elf carrying the most Calories. How many total Calories is that Elf carrying?
 
 */
-use std::{
-    collections::BinaryHeap,
-    cmp::Reverse,
-    error::Error,
-    fs::File,
-    io::{BufRead, BufReader},
-};
+use advent_lib::{iters::TopK, parse::read_sections};
+use std::{error::Error, fs::File, io::BufReader};
 fn main() -> Result<(), Box<dyn Error>> {
     let f = File::open("input.txt")?;
     let reader = BufReader::new(f);
-    let mut max = BinaryHeap::new();
-    let mut gnome_total_calories = 0;
-    for line in reader.lines() {
-        let line = line?;
-        if !line.is_empty() {
-            let num = u32::from_str_radix(&line, 10)?;
-            gnome_total_calories += num;
-        } else {
-            gnome_total_calories = 0;
-        }
+    let mut max = TopK::new(3);
+    for gnome in read_sections(reader) {
+        let gnome_total_calories = gnome?
+            .text
+            .lines()
+            .map(|line| line.parse::<u32>())
+            .sum::<Result<u32, _>>()?;
         max.push(gnome_total_calories);
     }
-    println!("Part 1 answer: {}", max.peek().expect("heap shouldn't be empty"));
-    let mut max_vec = max.into_vec();
-    max_vec.sort_by_key(|x| Reverse(*x)); // Sort in descending order.
-    let sum: u32 = max_vec[0..3].iter().sum();
+    let max_vec = max.into_sorted_vec(); // Descending order.
+    println!("Part 1 answer: {}", max_vec.first().expect("should have an elf"));
+    let sum: u32 = max_vec.iter().sum();
     println!("Part 2 answer: {:?}", sum);
 
     Ok(())
@@ -1,14 +1,26 @@
 mod data;
 mod parse;
 use crate::data::{Monkey, Operation, Value};
-use crate::parse::{parse_final, Span};
-use std::{error::Error, fs};
+use crate::parse::parse_final;
+use advent_lib::parse::{Input, ParseError};
+use miette::GraphicalReportHandler;
+use std::error::Error;
 fn main() -> Result<(), Box<dyn Error + Send + Sync>> {
     println!("Part 1 answer: {:#?}", part1::run("input.txt")?);
     println!("Part 2 answer: {:#?}", part2::run("input.txt")?);
     Ok(())
 }
 
+/// Draws `err` as a report pointing at the bad input, then passes it on.
+fn report(err: ParseError) -> ParseError {
+    let mut out = String::new();
+    GraphicalReportHandler::new()
+        .render_report(&mut out, &err)
+        .expect("Writing to a String can't fail");
+    eprintln!("{out}");
+    err
+}
+
 mod part1 {
     use super::*;
     fn do_round(monkeys: &mut Vec<Monkey>, inspections: &mut [usize]) {
@@ -55,9 +67,8 @@ mod part1 {
     }
 
     pub fn run(file: &str) -> Result<u32, Box<dyn Error + Send + Sync>> {
-        let input_str = fs::read_to_string(file)?;
-        let input = Span::new(Box::leak(Box::new(input_str)));
-        let mut monkeys = parse_final(input)?;
+        let input = Input::read(file)?;
+        let mut monkeys = parse_final(&input).map_err(report)?;
         let mut inspections = vec![0; monkeys.len()];
         for _ in 0..20 {
             do_round(&mut monkeys, &mut inspections);
@@ -124,9 +135,8 @@ mod part2 {
     }
 
     pub fn run(file: &str) -> Result<u64, Box<dyn Error + Send + Sync>> {
-        let input_str = fs::read_to_string(file)?;
-        let input = Span::new(Box::leak(Box::new(input_str)));
-        let mut monkeys = parse_final(input)?;
+        let input = Input::read(file)?;
+        let mut monkeys = parse_final(&input).map_err(report)?;
         let mut inspections = vec![0; monkeys.len()];
         for i in 0..10000 {
             do_round(&mut monkeys, &mut inspections);
@@ -171,4 +181,18 @@ mod tests {
         assert_eq!(part2::run("input.txt")?, 19309892877);
         Ok(())
     }
+
+    #[test]
+    fn test_parse_error() {
+        use miette::GraphicalTheme;
+        let input = Input::from("Monkey 0:\n  Starting items: 79, x\n");
+        let err = parse_final(&input).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 21));
+        assert_eq!(err.snippet, "  Starting items: 79, x");
+        let mut report = String::new();
+        GraphicalReportHandler::new_themed(GraphicalTheme::unicode_nocolor())
+            .render_report(&mut report, &err)
+            .unwrap();
+        assert!(report.contains("2 │   Starting items: 79, x"), "{report}");
+    }
 }
@@ -1,5 +1,5 @@
 use crate::data::{Monkey, Operation};
-use miette::GraphicalReportHandler;
+use advent_lib::parse::{nom::sections, Input, Span};
 use nom::{
     branch::alt,
     // bytes::complete::tag,
@@ -7,29 +7,21 @@ use nom::{
         complete::{char, digit1, line_ending, one_of},
         streaming::space1,
     },
-    combinator::{all_consuming, cut, eof},
-    error::{context, ContextError, ParseError},
-    multi::{many1, separated_list1},
+    combinator::{all_consuming, cut},
+    error::{context, ContextError, ParseError, VerboseError},
+    multi::separated_list1,
     sequence::{preceded, terminated, tuple},
     AsChar,
     IResult,
     InputTakeAtPosition,
 };
-use nom_locate::LocatedSpan;
-use nom_supreme::{
-    error::{BaseErrorKind, ErrorTree, GenericErrorTree},
-    tag::{complete::tag, TagError},
-};
+use nom_supreme::tag::{complete::tag, TagError};
 use std::{
     clone::Clone,
-    error::Error,
-    fmt::Debug,
     ops::Deref,
     str::{self, FromStr},
 };
 
-pub type Span<'a> = LocatedSpan<&'a str>;
-
 fn digit1_to_num<'a, I, O, E>(i: I) -> IResult<I, O, E>
 where
     O: FromStr,                               // To convert from digit1's string to value.
@@ -59,7 +51,7 @@ where
 
 fn starting_items<'a, O, E>(i: Span<'a>) -> IResult<Span<'a>, Vec<O>, E>
 where
-    E: ParseError<Span<'a>> + TagError<Span<'a>, &'a str>,
+    E: ParseError<Span<'a>> + TagError<Span<'a>, &'static str>,
     O: FromStr,
 {
     let (i, starting_items) = preceded(
@@ -72,7 +64,7 @@ where
 
 fn operation<'a, E>(i: Span<'a>) -> IResult<Span<'a>, Operation, E>
 where
-    E: ParseError<Span<'a>> + TagError<Span<'a>, &'a str>,
+    E: ParseError<Span<'a>> + TagError<Span<'a>, &'static str>,
 {
     let (i, (op, r)) = tuple((
         preceded(tag("  Operation: new = old "), one_of("*+")),
@@ -96,7 +88,7 @@ where
 
 fn parse_monkey<'a, E>(i: Span<'a>) -> IResult<Span<'a>, Monkey, E>
 where
-    E: ParseError<Span<'a>> + TagError<Span<'a>, &'a str>,
+    E: ParseError<Span<'a>> + TagError<Span<'a>, &'static str>,
 {
     let (i, _) = tuple((tag("Monkey "), terminated(digit1, char(':'))))(i)?;
     let (i, _) = line_ending(i)?;
@@ -107,7 +99,6 @@ where
     let (i, test_true_target) = preceded(tag("    If true: throw to monkey "), digit1_to_num)(i)?;
     let (i, _) = line_ending(i)?;
     let (i, test_false_target) = preceded(tag("    If false: throw to monkey "), digit1_to_num)(i)?;
-    let (i, _) = line_ending(i)?;
     Ok((
         i,
         Monkey {
@@ -120,20 +111,17 @@ where
     ))
 }
 
-// This is not the most efficient but used this to test context for error reporting
+// The context is there to test it in error reporting
 fn monkey_list<'a, E>(input: Span<'a>) -> IResult<Span<'a>, Vec<Monkey>, E>
 where
-    E: ParseError<Span<'a>> + ContextError<Span<'a>> + TagError<Span<'a>, &'a str>,
+    E: ParseError<Span<'a>> + ContextError<Span<'a>> + TagError<Span<'a>, &'static str>,
 {
-    let (input, mut monkeys) = many1(terminated(cut(parse_monkey), line_ending))(input)?;
-    let (input, monkey_final) = context("last monkey", terminated(cut(parse_monkey), eof))(input)?;
-    monkeys.push(monkey_final);
-    Ok((input, monkeys))
+    context("monkey", sections(cut(parse_monkey)))(input)
 }
 
 fn parse_input<'a, E>(input: Span<'a>) -> IResult<Span<'a>, Vec<Monkey>, E>
 where
-    E: ParseError<Span<'a>> + ContextError<Span<'a>> + TagError<Span<'a>, &'a str>,
+    E: ParseError<Span<'a>> + ContextError<Span<'a>> + TagError<Span<'a>, &'static str>,
 {
     match all_consuming(monkey_list)(input) {
         Ok((input, out)) => Ok((input, out)),
@@ -142,48 +130,8 @@ where
     }
 }
 
-#[derive(thiserror::Error, Debug, miette::Diagnostic)]
-#[error("bad input")]
-struct BadInput<'a> {
-    #[source_code]
-    src: &'a str,
-
-    #[label("{kind}")]
-    bad_bit: miette::SourceSpan,
-
-    kind: BaseErrorKind<&'a str, Box<dyn std::error::Error + Send + Sync>>,
-}
-
-pub fn parse_final(input: Span<'static>) -> Result<Vec<Monkey>, Box<dyn Error + Send + Sync>> {
-    let monkey_res = parse_input::<ErrorTree<Span>>(input);
-    let monkeys_handled_res = match monkey_res {
-        Ok(monkeys) => monkeys,
-        Err(nom::Err::Error(e) | nom::Err::Failure(e)) => {
-            match e {
-                GenericErrorTree::Base { location, kind } => {
-                    let err_length = match kind {
-                        BaseErrorKind::Expected(nom_supreme::error::Expectation::Tag(s)) => {
-                            s.len().into()
-                        }
-                        _ => 0.into(),
-                    };
-                    let offset = location.location_offset().into();
-                    let err = BadInput {
-                        src: *input,
-                        bad_bit: miette::SourceSpan::new(offset, err_length),
-                        kind,
-                    };
-                    let mut s = String::new();
-                    GraphicalReportHandler::new()
-                        .render_report(&mut s, &err)
-                        .unwrap();
-                    println!("{s}");
-                }
-                x => println!("{x}"),
-            }
-            return Err("Parsing error")?;
-        }
-        Err(nom::Err::Incomplete(_)) => unimplemented!(),
-    };
-    Ok(monkeys_handled_res.1)
+pub fn parse_final(input: &Input) -> Result<Vec<Monkey>, advent_lib::parse::ParseError> {
+    parse_input::<VerboseError<Span>>(input.span())
+        .map(|(_, monkeys)| monkeys)
+        .map_err(|e| advent_lib::parse::ParseError::from_nom(input, e))
 }
@@ -1,4 +1,4 @@
-use cord::{offset_to_cord, Cord};
+use advent_lib::{cord::NDCord, topology::Bounded};
 use ndarray::Array2;
 use std::{
     collections::{HashMap, HashSet},
@@ -7,7 +7,8 @@ use std::{
     io::{BufRead, BufReader},
     ops::Sub,
 };
-mod cord;
+
+type Cord = NDCord<usize, 2>;
 
 fn main() -> Result<(), Box<dyn Error>> {
     println!("Part 1 answer: {:#?}", part1::run("input.txt")?);
@@ -43,6 +44,10 @@ mod part2 {
     }
 }
 
+fn offset_to_cord(offset: usize, width: usize) -> Cord {
+    NDCord([offset % width, offset / width])
+}
+
 fn parse(file_name: &str) -> Result<(Cord, Cord, Array2<u8>), Box<dyn Error>> {
     let f = File::open(file_name)?;
     let reader = BufReader::new(f);
@@ -91,6 +96,9 @@ fn dft_unweighted_astar(start: Cord, end: Cord, input: &Array2<u8>) -> Option<us
     // Defining potential
     let potential = |node: Cord| node.manhattan_distance(&end);
 
+    // Index is (row,column) so the grid's width is its second dimension.
+    let bounds = Bounded::with_size(NDCord([input.dim().1, input.dim().0]));
+
     // Dijkstra
     let mut boundary_nodes = HashSet::from([start]);
     let mut distances = HashMap::from([(start, 0usize)]);
@@ -112,12 +120,12 @@ fn dft_unweighted_astar(start: Cord, end: Cord, input: &Array2<u8>) -> Option<us
         }
 
         // Increase scope of neighbors to neighbors of `cur_node`
-        for neighbor in cur_node.neumann_neighborhood(1, input.dim().1, input.dim().0) {
+        for neighbor in cur_node.neumann_neighborhood_in(1, &bounds) {
             // NEW for advent of code
             // Confirm that neighbor is valid (height difference is <= 1 greater before continuing.
             // Index is (row,column) not (x,y) or (column, row). See above for loop and below line.
-            if input[[neighbor.1, neighbor.0]] > input[[cur_node.1, cur_node.0]]
-                && input[[neighbor.1, neighbor.0]].sub(input[[cur_node.1, cur_node.0]]) > 1
+            if input[[neighbor[1], neighbor[0]]] > input[[cur_node[1], cur_node[0]]]
+                && input[[neighbor[1], neighbor[0]]].sub(input[[cur_node[1], cur_node[0]]]) > 1
             {
                 continue;
             }
@@ -146,8 +154,8 @@ mod tests {
     #[test]
     fn test_test_parse() -> Result<(), Box<dyn Error>> {
         let out = parse("inputtest.txt")?;
-        assert_eq!(out.0, Cord(0, 0));
-        assert_eq!(out.1, Cord(5, 2));
+        assert_eq!(out.0, NDCord([0, 0]));
+        assert_eq!(out.1, NDCord([5, 2]));
         println!(
             "{}",
             out.2
@@ -167,8 +175,8 @@ mod tests {
     #[test]
     fn test_parse() -> Result<(), Box<dyn Error>> {
         let out = parse("input.txt")?;
-        assert_eq!(out.0, Cord(0, 20));
-        assert_eq!(out.1, Cord(138, 20));
+        assert_eq!(out.0, NDCord([0, 20]));
+        assert_eq!(out.1, NDCord([138, 20]));
         println!(
             "{}",
             out.2
@@ -1,7 +1,7 @@
 use crate::data::{Packet, Pair};
+use advent_lib::parse::Input;
 use itertools::Itertools;
 use std::error::Error;
-use std::fs;
 mod data;
 mod parse;
 
@@ -14,9 +14,8 @@ fn main() -> Result<(), Box<dyn Error>> {
 mod part1 {
     use super::*;
     pub fn run(file: &str) -> Result<usize, Box<dyn Error>> {
-        let input_str = fs::read_to_string(file)?;
-        let input = Box::leak(Box::new(input_str));
-        let (_input, pairs) = parse::parse_input(input)?;
+        let input = Input::read(file)?;
+        let pairs = input.parse_with(parse::parse_input)?;
         Ok(pairs
             .iter()
             .enumerate()
@@ -35,11 +34,10 @@ mod part1 {
 mod part2 {
     use super::*;
     pub fn run(file: &str) -> Result<usize, Box<dyn Error>> {
-        let input_str = fs::read_to_string(file)?;
-        let input = Box::leak(Box::new(input_str));
+        let input = Input::read(file)?;
         let divider_packet_1 = Packet::List(vec![Packet::List(vec![Packet::Integer(2)])]);
         let divider_packet_2 = Packet::List(vec![Packet::List(vec![Packet::Integer(6)])]);
-        let (_input, mut pairs) = parse::parse_input(input)?;
+        let mut pairs = input.parse_with(parse::parse_input)?;
         pairs.push(Pair {
             left: divider_packet_1.clone(),
             right: divider_packet_2.clone(),
@@ -69,9 +67,8 @@ mod tests {
     use super::*;
     #[test]
     fn test_part1_out_parse() -> Result<(), Box<dyn Error>> {
-        let input_str = fs::read_to_string("inputtest.txt")?;
-        let input = Box::leak(Box::new(input_str));
-        let (_, pairs) = parse::parse_input(input)?;
+        let input = Input::read("inputtest.txt")?;
+        let pairs = input.parse_with(parse::parse_input)?;
         // pairs.iter().for_each(|x| print!("{x}\n"));
         for pair in pairs {
             print!("{}{:?}\n", pair, pair.left.cmp(&pair.right));
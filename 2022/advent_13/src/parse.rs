@@ -1,11 +1,12 @@
 use crate::data::{Packet, Pair};
+use advent_lib::parse::nom::sections;
 use nom::{
     branch::alt,
     bytes::complete::tag,
     character::complete::{digit1, line_ending},
     combinator::all_consuming,
     error::ParseError,
-    multi::{separated_list0, separated_list1},
+    multi::separated_list0,
     sequence::{delimited, terminated},
     IResult, Parser,
 };
@@ -53,10 +54,10 @@ fn packet(input: &str) -> IResult<&str, Packet> {
 
 fn pair(input: &str) -> IResult<&str, Pair> {
     let (input, left) = terminated(packet, line_ending)(input)?;
-    let (input, right) = terminated(packet, line_ending)(input)?;
+    let (input, right) = packet(input)?;
     Ok((input, Pair { left, right }))
 }
 
 pub fn parse_input(input: &str) -> IResult<&str, Vec<Pair>> {
-    all_consuming(separated_list1(line_ending, pair))(input)
+    all_consuming(sections(pair))(input)
 }
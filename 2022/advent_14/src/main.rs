@@ -1,9 +1,12 @@
 use crate::data::{Sand, SandPosType};
-use advent_lib::cord::NDCord;
+use advent_lib::{
+    cord::{polyline, NDCord},
+    parse::Input,
+};
 use std::{
     collections::HashSet,
     error::Error,
-    fs::{self, OpenOptions},
+    fs::OpenOptions,
     io::Write,
 };
 
@@ -21,27 +24,24 @@ mod part1 {
     const SAND_START: Cord<SandPosType> = Cord::new([500, 0]);
     use super::*;
     pub fn run(file: &str) -> Result<usize, Box<dyn Error>> {
-        let input_str = fs::read_to_string(file)?;
-        let input = Box::leak(Box::new(input_str));
-        let (_, parsed_input) = parse::parse_input(input)?;
+        let input = Input::read(file)?;
+        let parsed_input = input.parse_with(parse::parse_input)?;
 
         // Add all positions of any rock to the rocks set.
         let mut rocks: HashSet<Cord<SandPosType>> = HashSet::new();
         for connected in parsed_input {
-            for cord_pair in connected.windows(2) {
-                rocks.extend(cord_pair[0].interpolate(&cord_pair[1]));
-            }
+            rocks.extend(polyline(connected));
         }
 
         // Find bottom level
-        let bottom = rocks.iter().max_by_key(|&x| x[1]).unwrap()[1];
+        let bottom = rocks.iter().map(Cord::y).max().unwrap();
 
         let mut sands = HashSet::new();
         'newsand: loop {
             let mut sand = Sand { pos: SAND_START };
             while sand.fall(&rocks, &sands) {
                 // If sand falls off the edge stop adding sand.
-                if sand.pos[1] > bottom {
+                if sand.pos.y() > bottom {
                     break 'newsand;
                 }
             }
@@ -56,23 +56,20 @@ mod part2 {
 
     use super::*;
     pub fn run(file: &str) -> Result<usize, Box<dyn Error>> {
-        let input_str = fs::read_to_string(file)?;
-        let input = Box::leak(Box::new(input_str));
-        let (_, parsed_input) = parse::parse_input(input)?;
+        let input = Input::read(file)?;
+        let parsed_input = input.parse_with(parse::parse_input)?;
 
         // Add all positions of any rock to the rocks set.
         let mut rocks: HashSet<Cord<SandPosType>> = HashSet::new();
         for connected in parsed_input {
-            for cord_pair in connected.windows(2) {
-                rocks.extend(cord_pair[0].interpolate(&cord_pair[1]));
-            }
+            rocks.extend(polyline(connected));
         }
 
         // Find bottom level. It is 2 below bottom rock.
-        let bottom = rocks.iter().max_by_key(|&x| x[1]).unwrap()[1] + 2;
+        let bottom = rocks.iter().map(Cord::y).max().unwrap() + 2;
         // 200's are buffer so overrun doesn't go to the void.
-        let left = rocks.iter().min_by_key(|&x| x[0]).unwrap()[0] - 200;
-        let right = rocks.iter().max_by_key(|&x| x[0]).unwrap()[0] + 200;
+        let left = rocks.iter().map(Cord::x).min().unwrap() - 200;
+        let right = rocks.iter().map(Cord::x).max().unwrap() + 200;
 
         let bottom_left = Cord::from([left, bottom]);
         let bottom_right = Cord::from([right, bottom]);
@@ -86,7 +83,7 @@ mod part2 {
 
             while sand.fall(&rocks, &sands) {
                 // If sand falls off covers hold stop adding sand.
-                if sand.pos[1] == SAND_START[1] {
+                if sand.pos.y() == SAND_START.y() {
                     break 'newsand;
                 }
             }
@@ -135,9 +132,8 @@ mod tests {
     use super::*;
     #[test]
     fn test_part1_out_parse() -> Result<(), Box<dyn Error>> {
-        let input_str = fs::read_to_string("inputtest.txt")?;
-        let input = Box::leak(Box::new(input_str));
-        let (_, parsed_input) = parse::parse_input::<usize>(input)?;
+        let input = Input::read("inputtest.txt")?;
+        let parsed_input = input.parse_with(parse::parse_input::<usize>)?;
         dbg!(parsed_input);
         Ok(())
     }
@@ -2,7 +2,8 @@ use crate::cord::Cord;
 use crate::data::SortableRangeInclusive;
 #[allow(unused_imports)]
 use advent_15::dbc;
-use std::{error::Error, fs};
+use advent_lib::parse::Input;
+use std::error::Error;
 mod cord;
 mod data;
 mod parse;
@@ -17,9 +18,8 @@ mod part1 {
     use super::*;
     use std::collections::HashSet;
     pub fn run(file: &str, row: isize) -> Result<usize, Box<dyn Error>> {
-        let input_str = fs::read_to_string(file)?;
-        let input = Box::leak(Box::new(input_str));
-        let (_, mut parsed_input) = parse::parse_input::<isize>(input)?;
+        let input = Input::read(file)?;
+        let mut parsed_input = input.parse_with(parse::parse_input::<isize>)?;
         let mut safe_spots = HashSet::new();
         for pair in &mut parsed_input {
             let radius = pair.sensor.manhattan_distance(&pair.beacon);
@@ -52,9 +52,8 @@ mod part1 {
     // Takes too long
     #[allow(dead_code)]
     pub fn run_naive(file: &str, row: isize) -> Result<usize, Box<dyn Error>> {
-        let input_str = fs::read_to_string(file)?;
-        let input = Box::leak(Box::new(input_str));
-        let (_, parsed_input) = parse::parse_input::<isize>(input)?;
+        let input = Input::read(file)?;
+        let parsed_input = input.parse_with(parse::parse_input::<isize>)?;
         let mut safe_spots = HashSet::new();
         for pair in parsed_input {
             let radius = pair.sensor.manhattan_distance(&pair.beacon);
@@ -69,11 +68,37 @@ mod part1 {
 
 mod part2 {
     use super::*;
+    use advent_lib::{
+        cord::NDCord,
+        manhattan::{uncovered_in_box, ManhattanBall},
+    };
     use std::{collections::HashSet, vec};
     pub fn run(file: &str, upper_limit: isize) -> Result<usize, Box<dyn Error>> {
-        let input_str = fs::read_to_string(file)?;
-        let input = Box::leak(Box::new(input_str));
-        let (_, parsed_input) = parse::parse_input::<isize>(input)?;
+        let input = Input::read(file)?;
+        let parsed_input = input.parse_with(parse::parse_input::<isize>)?;
+
+        // Each sensor covers every cell at least as close as its beacon.
+        let sensors: Vec<_> = parsed_input
+            .iter()
+            .map(|pair| {
+                ManhattanBall::reaching(
+                    NDCord([pair.sensor.0, pair.sensor.1]),
+                    NDCord([pair.beacon.0, pair.beacon.1]),
+                )
+            })
+            .collect();
+        let distress_beacon =
+            uncovered_in_box(&sensors, NDCord([0, 0]), NDCord([upper_limit, upper_limit]))
+                .next()
+                .expect("A cell no sensor covers");
+        Ok(usize::try_from(distress_beacon[0] * 4000000 + distress_beacon[1]).unwrap())
+    }
+
+    // Scans every row. Slower than finding the gap between sensors geometrically.
+    #[allow(dead_code)]
+    pub fn run_row_scan(file: &str, upper_limit: isize) -> Result<usize, Box<dyn Error>> {
+        let input = Input::read(file)?;
+        let parsed_input = input.parse_with(parse::parse_input::<isize>)?;
 
         let mut row_ranges = vec![vec![]; (upper_limit + 1).try_into().unwrap()];
         // Add all sensors to the rows they affect
@@ -117,8 +142,7 @@ mod part2 {
     // Takes too long and allocates too much.
     #[allow(dead_code, unused_variables)]
     pub fn run_naive(file: &str, upper_limit: isize) -> Result<usize, Box<dyn Error>> {
-        let input_str = fs::read_to_string(file)?;
-        let input = Box::leak(Box::new(input_str));
+        let input = Input::read(file)?;
         let possible_locations = Cord(0, 0)
             .interpolate(&Cord(upper_limit, upper_limit))
             .map(|x| {
@@ -138,9 +162,8 @@ mod tests {
     use super::*;
     #[test]
     fn test_part1_out_parse() -> Result<(), Box<dyn Error>> {
-        let input_str = fs::read_to_string("inputtest.txt")?;
-        let input = Box::leak(Box::new(input_str));
-        let (_, parsed_input) = parse::parse_input::<isize>(input)?;
+        let input = Input::read("inputtest.txt")?;
+        let parsed_input = input.parse_with(parse::parse_input::<isize>)?;
         dbc!(parsed_input);
         Ok(())
     }
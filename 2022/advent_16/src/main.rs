@@ -1,5 +1,5 @@
 #[allow(unused_imports)]
-use advent_lib::{algorithms, dbc, parse::read_and_leak};
+use advent_lib::{algorithms, dbc, parse::Input};
 use cached::proc_macro::cached;
 use std::{
     collections::{hash_map::DefaultHasher, BTreeMap},
@@ -116,8 +116,8 @@ mod part1 {
 
     const MAX_MINUTES: u32 = 30;
     pub fn run(file: &str) -> Result<u32, Box<dyn Error>> {
-        let input = read_and_leak(file)?;
-        let (flowrates, mut distances) = parse::parse_input(input);
+        let input = Input::read(file)?;
+        let (flowrates, mut distances) = parse::parse_input(&input);
         algorithms::floyd_warshall(flowrates.keys().copied(), &mut distances); // calc distances
 
         let distances = distances
@@ -147,8 +147,8 @@ mod part2 {
 
     const MAX_MINUTES: u32 = 26;
     pub fn run(file: &str) -> Result<u32, Box<dyn Error>> {
-        let input = read_and_leak(file)?;
-        let (flowrates, mut distances) = parse::parse_input(input);
+        let input = Input::read(file)?;
+        let (flowrates, mut distances) = parse::parse_input(&input);
         algorithms::floyd_warshall(flowrates.keys().copied(), &mut distances); // calc distances
 
         let distances = distances
@@ -179,8 +179,8 @@ mod tests {
 
     #[test]
     fn test_part1_out_parse() -> Result<(), Box<dyn Error>> {
-        let input = read_and_leak("inputtest.txt")?;
-        let (flowrates, distances) = parse::parse_input(input);
+        let input = Input::read("inputtest.txt")?;
+        let (flowrates, distances) = parse::parse_input(&input);
         dbc!(flowrates, distances);
         Ok(())
     }
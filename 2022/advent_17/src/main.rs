@@ -1,6 +1,6 @@
 #[allow(unused_imports)]
 use advent_lib::dbc;
-use advent_lib::{cord::NDCord, parse::read_and_leak};
+use advent_lib::{cord::NDCord, parse::Input};
 use data::{Action, Rock};
 use std::{
     collections::{BTreeSet, HashSet},
@@ -238,8 +238,8 @@ mod part1 {
     use crate::data::{Grid, TYPES_OF_ROCK};
 
     pub fn run(file_name: &str) -> Result<usize, Box<dyn Error>> {
-        let input = read_and_leak(file_name)?;
-        let actions = parse::parse_input(input);
+        let input = Input::read(file_name)?;
+        let actions = parse::parse_input(&input);
         let mut actions = actions.into_iter().cycle();
         let mut grid = Grid {
             occupied_cells: NDCord([0, 0]).interpolate(&NDCord([8, 0])).collect(),
@@ -338,8 +338,8 @@ mod part2 {
     const TARGET_RND_NUM: usize = 1000000000000;
 
     pub fn run(file_name: &str) -> Result<usize, Box<dyn Error>> {
-        let input = read_and_leak(file_name)?;
-        let actions = parse::parse_input(input);
+        let input = Input::read(file_name)?;
+        let actions = parse::parse_input(&input);
         let action_len = actions.len();
         let mut actions = actions.into_iter().cycle();
         let mut grid = Grid {
@@ -493,8 +493,8 @@ mod tests {
 
     #[test]
     fn test_part1_out_parse() -> Result<(), Box<dyn Error>> {
-        let input = read_and_leak("inputtest.txt")?;
-        let actions = parse::parse_input(input);
+        let input = Input::read("inputtest.txt")?;
+        let actions = parse::parse_input(&input);
         dbc!(actions);
         Ok(())
     }
@@ -1,7 +1,4 @@
-use advent_lib::{
-    cord::NDCord,
-    parse::{nom::parse_from, read_and_leak},
-};
+use advent_lib::{cord::NDCord, parse::Input};
 use std::error::Error;
 
 fn main() -> Result<(), Box<dyn Error>> {
@@ -14,23 +11,16 @@ type Voxel = NDCord<isize, 3>;
 
 mod parse {
     use nom::{
-        bytes::complete::tag,
-        character::complete::{digit1, line_ending},
-        combinator::all_consuming,
+        character::complete::{line_ending, not_line_ending},
+        combinator::{all_consuming, map_res},
         multi::separated_list1,
-        sequence::{terminated, tuple},
+        sequence::terminated,
         IResult,
     };
 
     use super::*;
     fn voxel(input: &str) -> IResult<&str, Voxel> {
-        let (input, out) = tuple((
-            (terminated(parse_from(digit1), tag(","))),
-            (terminated(parse_from(digit1), tag(","))),
-            parse_from(digit1),
-        ))(input)?;
-        let out = [out.0, out.1, out.2];
-        Ok((input, out.into()))
+        map_res(not_line_ending, str::parse)(input)
     }
     pub fn parse_input(input: &str) -> IResult<&str, Vec<Voxel>> {
         all_consuming(terminated(separated_list1(line_ending, voxel), line_ending))(input)
@@ -42,8 +32,8 @@ mod part1 {
     use crate::parse::parse_input;
 
     pub fn run(file_name: &str) -> Result<usize, Box<dyn Error>> {
-        let input = read_and_leak(file_name)?;
-        let (_, mut voxels) = parse_input(input)?;
+        let input = Input::read(file_name)?;
+        let mut voxels = input.parse_with(parse_input)?;
         // Sort so faster to find voxels later.
         voxels.sort();
         let non_blob_neighbors = |voxel: Voxel| {
@@ -63,49 +53,32 @@ mod part1 {
 mod part2 {
     use super::*;
     use crate::parse::parse_input;
-    use advent_lib::algorithms::flood_fill;
+    use advent_lib::{algorithms::flood_fill, topology::Bounded};
 
     pub fn run(file_name: &str) -> Result<usize, Box<dyn Error>> {
-        let input = read_and_leak(file_name)?;
-        let (_, mut voxels) = parse_input(input)?;
+        let input = Input::read(file_name)?;
+        let mut voxels = input.parse_with(parse_input)?;
         let mut voxel_exposed = ((0..voxels.len()).map(|_| 0)).collect::<Vec<_>>();
         // Sort so faster to find voxels later.
         voxels.sort();
 
-        let (min_x, max_x) = (
-            voxels.iter().min_by(|x, y| x[0].cmp(&y[0])).unwrap()[0],
-            voxels.iter().max_by(|x, y| x[0].cmp(&y[0])).unwrap()[0],
-        );
-        let (min_y, max_y) = (
-            voxels.iter().min_by(|x, y| x[1].cmp(&y[1])).unwrap()[1],
-            voxels.iter().max_by(|x, y| x[1].cmp(&y[1])).unwrap()[1],
-        );
-        let (min_z, max_z) = (
-            voxels.iter().min_by(|x, y| x[2].cmp(&y[2])).unwrap()[2],
-            voxels.iter().max_by(|x, y| x[2].cmp(&y[2])).unwrap()[2],
-        );
+        // Bounds of the blob grown by one so it's surrounded by air.
+        // Air further out doesn't touch the blob so it's left out of the search.
+        // For example in 1D everything within | would be a valid neighbor: A A | A X A X X A | A A
+        let (min, max) = NDCord::extents_iter(voxels.iter().copied()).ok_or("No voxels")?;
+        let bounds = Bounded::new(min - NDCord([1; 3]), max + NDCord([1; 3]));
 
         // Function to find the neighbors of a given voxel that aren't part of the lava blob.
         let non_blob_neighbors = |voxel: Voxel| {
             voxel
-                .neumann_neighborhood(1)
+                .neumann_neighborhood_in(1, &bounds)
                 // Filter out blob elements from neighbors.
                 .filter(|node| voxels.binary_search(node).is_err())
-                // Filter out nodes far beyond the blob leaving only the blob and immediate exposing air.
-                // For example in 1D everything within | would be a valid neighbor: A A | A X A X X A | A A
-                .filter(|node| {
-                    min_x - 1 <= node[0]
-                        && node[0] <= max_x + 1
-                        && min_y - 1 <= node[1]
-                        && node[1] <= max_y + 1
-                        && min_z - 1 <= node[2]
-                        && node[2] <= max_z + 1
-                })
                 .collect::<Vec<_>>()
                 .into_iter()
         };
         // Starting outside the blob's bounds.
-        let start = [max_x + 1, max_y + 1, max_z + 1].into();
+        let start = bounds.max;
         // Find all air connected to the outside.
         let external_air = flood_fill(start, non_blob_neighbors);
 
@@ -128,8 +101,8 @@ mod tests {
 
     #[test]
     fn test_part1_out_parse() -> Result<(), Box<dyn Error>> {
-        let input = read_and_leak("inputtest.txt")?;
-        let (_, parsed) = parse::parse_input(input).unwrap();
+        let input = Input::read("inputtest.txt")?;
+        let parsed = input.parse_with(parse::parse_input).unwrap();
         dbc!(parsed);
         Ok(())
     }
@@ -1,4 +1,4 @@
-use advent_lib::parse::read_and_leak;
+use advent_lib::parse::Input;
 use std::error::Error;
 
 mod data;
@@ -13,8 +13,8 @@ mod part1 {
     use std::{cell::Cell, collections::BTreeMap, rc::Rc};
 
     pub fn run(file_name: &str) -> Result<usize, Box<dyn Error>> {
-        let input = read_and_leak(file_name)?;
-        let blueprints = parse_input(input)?;
+        let input = Input::read(file_name)?;
+        let blueprints = parse_input(&input)?;
         let starting_rounds = blueprints.into_iter().map(|blueprint| {
             [
                 Round {
@@ -62,8 +62,8 @@ mod part2 {
     use std::{cell::Cell, collections::BTreeMap, rc::Rc};
 
     pub fn run(file_name: &str) -> Result<usize, Box<dyn Error>> {
-        let input = read_and_leak(file_name)?;
-        let blueprints = parse_input(input)?;
+        let input = Input::read(file_name)?;
+        let blueprints = parse_input(&input)?;
         let starting_rounds = blueprints.into_iter().take(3).map(|blueprint| {
             [
                 Round {
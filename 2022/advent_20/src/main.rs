@@ -1,18 +1,18 @@
 use std::error::Error;
 
 mod parse {
-    use advent_lib::parse::yap::{all_consuming, line_ending, signed_digit1, AllConsuming};
-    use yap::{IntoTokens, Tokens};
+    use advent_lib::parse::{
+        yap::{int, line_ending, sep_by, try_all_consuming},
+        ParseError,
+    };
+    use yap::IntoTokens;
     type Cord = isize;
 
-    pub fn parse_input(input: &str) -> Result<Vec<Cord>, AllConsuming<String>> {
-        all_consuming(&mut input.into_tokens(), |t| {
-            let res = t
-                .sep_by(signed_digit1, |t| line_ending(t).is_some())
-                .map(|x| x.unwrap())
-                .collect::<Vec<_>>();
-            line_ending(t);
-            res
+    pub fn parse_input(input: &str) -> Result<Vec<Cord>, ParseError> {
+        try_all_consuming(&mut input.into_tokens(), |t| {
+            let res = sep_by(t, int, |t| line_ending(t).is_ok())?;
+            line_ending(t).ok();
+            Ok(res)
         })
     }
 }
@@ -20,11 +20,11 @@ mod parse {
 mod part1 {
     use super::*;
     use crate::parse::parse_input;
-    use advent_lib::parse::read_and_leak;
+    use advent_lib::parse::Input;
 
     pub fn run(file_name: &str) -> Result<isize, Box<dyn Error>> {
-        let input = read_and_leak(file_name)?;
-        let cords = parse_input(input)?;
+        let input = Input::read(file_name)?;
+        let cords = parse_input(&input)?;
 
         // Each has (id, value)
         let mut identity_cords = cords.into_iter().enumerate().collect::<Vec<_>>();
@@ -57,13 +57,13 @@ mod part1 {
 mod part2 {
     use super::*;
     use crate::parse::parse_input;
-    use advent_lib::parse::read_and_leak;
+    use advent_lib::parse::Input;
 
     const DECRYPT_KEY: isize = 811589153;
 
     pub fn run(file_name: &str) -> Result<isize, Box<dyn Error>> {
-        let input = read_and_leak(file_name)?;
-        let cords = parse_input(input)?
+        let input = Input::read(file_name)?;
+        let cords = parse_input(&input)?
             .into_iter()
             .map(|x| x * DECRYPT_KEY)
             .collect::<Vec<_>>();
@@ -122,11 +122,11 @@ mod parse {
 mod part1 {
     use super::*;
     use crate::{data::Val, parse::parse_input};
-    use advent_lib::parse::read_and_leak;
+    use advent_lib::parse::Input;
 
     pub fn run(file_name: &str) -> Result<Val, Box<dyn Error>> {
-        let input = read_and_leak(file_name)?;
-        let (_, monkeys) = parse_input(input)?;
+        let input = Input::read(file_name)?;
+        let monkeys = input.parse_with(parse_input)?;
         Ok(monkeys["root"].eval(&monkeys))
     }
 }
@@ -137,11 +137,11 @@ mod part2 {
         data::{Monkey, Val},
         parse::parse_input,
     };
-    use advent_lib::parse::read_and_leak;
+    use advent_lib::parse::Input;
 
     pub fn run(file_name: &str) -> Result<Val, Box<dyn Error>> {
-        let input = read_and_leak(file_name)?;
-        let (_, mut monkeys) = parse_input(input)?;
+        let input = Input::read(file_name)?;
+        let mut monkeys = input.parse_with(parse_input)?;
         let Monkey::Op((_, left, right)) = monkeys["root"] else { panic!("Root has 2 children") };
         let target = dbg!(monkeys[right].eval(&monkeys));
         // Brute force check using newton's method
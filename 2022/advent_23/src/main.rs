@@ -1,63 +1,28 @@
-use data::{Dir, Map, Pos};
-use enum_iterator::All;
-use std::{collections::BTreeMap, error::Error, iter::Cycle};
+use advent_lib::dir::{Compass, Direction};
+use data::{Map, Pos};
+use std::{array, collections::BTreeMap, error::Error, iter::Cycle};
 
 mod data {
-    use advent_lib::cord::NDCord;
-    use enum_iterator::Sequence;
+    use advent_lib::{cord::NDCord, dir::Compass};
     use std::collections::BTreeSet;
 
     pub type Val = isize;
     pub type Pos = NDCord<Val, 2>;
     pub type Map = BTreeSet<Pos>;
 
-    #[derive(Clone, Copy, Debug, Sequence)]
-    pub enum Dir {
-        North,
-        South,
-        West,
-        East,
-    }
-
-    impl Dir {
-        pub fn move_pos(&self, pos: Pos) -> Pos {
-            match self {
-                Dir::North => pos + [0, -1].into(),
-                Dir::South => pos + [0, 1].into(),
-                Dir::East => pos + [1, 0].into(),
-                Dir::West => pos + [-1, 0].into(),
-            }
-        }
-
-        /// Check if elf in this direction.
-        pub fn elf_in_dir(&self, elf: Pos, map: &Map) -> bool {
-            let top_left = elf + [-1, -1].into();
-            let top_right = elf + [1, -1].into();
-            let bottom_left = elf + [-1, 1].into();
-            let bottom_right = elf + [1, 1].into();
-            match self {
-                Dir::North => (top_left)
-                    .interpolate(&top_right)
-                    .any(|x| map.get(&x).is_some()),
-                Dir::South => (bottom_left)
-                    .interpolate(&bottom_right)
-                    .any(|x| map.get(&x).is_some()),
-                Dir::East => (top_right)
-                    .interpolate(&bottom_right)
-                    .any(|x| map.get(&x).is_some()),
-                Dir::West => (top_left)
-                    .interpolate(&bottom_left)
-                    .any(|x| map.get(&x).is_some()),
-            }
-        }
-    }
+    /// Order the directions are considered in on the first round.
+    pub const CHECK_ORDER: [Compass; 4] =
+        [Compass::North, Compass::South, Compass::West, Compass::East];
 }
 
 mod parse {
     use crate::data::{Map, Pos};
     use advent_lib::{
-        cord::NDCord,
-        parse::yap::{all_consuming, line_ending, AllConsuming},
+        grid::{render_cells, RenderMode},
+        parse::{
+            yap::{all_consuming, line_ending},
+            ParseError,
+        },
     };
     use std::{collections::BTreeSet, fs::File, io::Write, path::Path};
     use yap::{IntoTokens, Tokens};
@@ -76,7 +41,7 @@ mod parse {
                     break;
                 }
             }
-            if let None = line_ending(input) {
+            if line_ending(input).is_err() {
                 break;
             }
             cursor[0] = 0;
@@ -84,35 +49,18 @@ mod parse {
         }
         out
     }
-    pub fn parse_input(input: &str) -> Result<Map, AllConsuming<String>> {
+    pub fn parse_input(input: &str) -> Result<Map, ParseError> {
         all_consuming(&mut input.into_tokens(), initial_map)
     }
 
     #[allow(dead_code)]
-    pub fn log_state(file: &Path, map: &Map) {
+    pub fn log_state(file: &Path, map: &Map, mode: RenderMode) {
         let mut file = File::create(file).unwrap();
-        let mut extents = NDCord::extents_iter(map.iter().copied()).unwrap();
-        extents.0.swap(0, 1);
-        extents.1.swap(0, 1);
-        extents
-            .0
-            .interpolate(&extents.1)
-            .map(|mut x| {
-                x.swap(0, 1);
-                if map.get(&x).is_none() {
-                    write!(file, ".").unwrap();
-                } else {
-                    write!(file, "#").unwrap();
-                }
-                if x.0[0] == extents.1[1] {
-                    writeln!(file).unwrap();
-                }
-            })
-            .for_each(drop);
+        write!(file, "{}", render_cells(map.iter().copied(), mode)).unwrap();
     }
 }
 
-fn do_round(map: &mut Map, global_next_dir: &mut Cycle<All<Dir>>) {
+fn do_round(map: &mut Map, global_next_dir: &mut Cycle<array::IntoIter<Compass, 4>>) {
     let transitions: BTreeMap<Pos, Pos> = map
         .iter()
         .map(|&elf| {
@@ -128,9 +76,9 @@ fn do_round(map: &mut Map, global_next_dir: &mut Cycle<All<Dir>>) {
             let mut next_dir = global_next_dir.clone();
             for _ in 0..4 {
                 let dir = next_dir.next().unwrap();
-                let res = dir.elf_in_dir(elf, &map);
-                if !res {
-                    return (elf, dir.move_pos(elf));
+                let elf_in_dir = dir.in_front(elf).any(|x| map.contains(&x));
+                if !elf_in_dir {
+                    return (elf, elf + dir.to_velocity());
                 }
             }
 
@@ -159,17 +107,17 @@ fn do_round(map: &mut Map, global_next_dir: &mut Cycle<All<Dir>>) {
 mod part1 {
     use super::*;
     use crate::{
-        data::{Dir, Val},
+        data::{Val, CHECK_ORDER},
         parse::parse_input,
     };
-    use advent_lib::{cord::NDCord, parse::read_and_leak};
+    use advent_lib::{cord::NDCord, parse::Input};
 
     const ROUND_END: usize = 10;
 
     pub fn run(file_name: &str) -> Result<Val, Box<dyn Error>> {
-        let input = read_and_leak(file_name)?;
-        let mut map = parse_input(input)?;
-        let mut global_next_dir = enum_iterator::all::<Dir>().cycle();
+        let input = Input::read(file_name)?;
+        let mut map = parse_input(&input)?;
+        let mut global_next_dir = CHECK_ORDER.into_iter().cycle();
         (0..ROUND_END).for_each(|_| do_round(&mut map, &mut global_next_dir));
         let extents = NDCord::extents_iter(map.iter().copied()).expect("nonempty");
         Ok(extents
@@ -185,15 +133,15 @@ mod part1 {
 mod part2 {
     use super::*;
     use crate::{
-        data::{Dir, Val},
+        data::{Val, CHECK_ORDER},
         parse::parse_input,
     };
-    use advent_lib::parse::read_and_leak;
+    use advent_lib::parse::Input;
 
     pub fn run(file_name: &str) -> Result<Val, Box<dyn Error>> {
-        let input = read_and_leak(file_name)?;
-        let map = parse_input(input)?;
-        let mut global_next_dir = enum_iterator::all::<Dir>().cycle();
+        let input = Input::read(file_name)?;
+        let map = parse_input(&input)?;
+        let mut global_next_dir = CHECK_ORDER.into_iter().cycle();
         let mut map_before = map.clone();
         let mut map_after = map;
         let mut countdown = 4; // try all 4 dirs before giving up
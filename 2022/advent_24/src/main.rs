@@ -1,7 +1,7 @@
 use std::error::Error;
 
 mod data {
-    use advent_lib::{cord::NDCord, dir::Dir};
+    use advent_lib::{cord::NDCord, dir::Dir, topology::Toroidal};
     use ndarray::{Array2, Axis};
     use std::{
         fmt::{Debug, Display},
@@ -84,42 +84,25 @@ mod data {
         )
         .expect("Valid shape");
 
+        // Blizzards wrap around within the walls.
+        let inside_walls = Toroidal::new(
+            Pos::from([1, 1]),
+            Pos::from([map.dim().0, map.dim().1].map(|x| isize::try_from(x).unwrap() - 2)),
+        );
         // Move all blizzards to their new positions in the new_map.
         for (idx, cell) in map.indexed_iter() {
             if let Cell::Blizzards(blizzards) = cell {
+                let pos = Pos::from([idx.0, idx.1].map(|x| isize::try_from(x).unwrap()));
                 for blizzard in blizzards {
-                    let mut wrap_dest = None;
-                    if let Some(to_cell) = new_map.get_mut(
-                        (blizzard.to_velocity()
-                            + [
-                                isize::try_from(idx.0).unwrap(),
-                                isize::try_from(idx.1).unwrap(),
-                            ]
-                            .into())
-                        .0
-                        .map(|x| usize::try_from(x).unwrap()),
-                    ) {
-                        match to_cell {
-                            Cell::Wall => {
-                                // Save where the blizzard is going to wrap at for later.
-                                wrap_dest = match blizzard {
-                                    Dir::Right => Some([1, idx.1]),
-                                    Dir::Down => Some([idx.0, 1]),
-                                    Dir::Left => Some([map.dim().0 - 2, idx.1]),
-                                    Dir::Up => Some([idx.0, map.dim().1 - 2]),
-                                }
-                            }
-                            x @ Cell::Floor => *x = Cell::Blizzards(vec![*blizzard]),
-                            Cell::Blizzards(b) => b.push(*blizzard),
-                        }
-                    }
-                    // Handle the wall case
-                    if let Some(wrap_dest) = wrap_dest {
-                        match &mut new_map[wrap_dest] {
-                                x @ Cell::Floor => *x = Cell::Blizzards(vec![*blizzard]),
-                                Cell::Blizzards(b) => b.push(*blizzard),
-                                Cell::Wall => unreachable!("No walls within the box. Blizzard should wrap to a non-wall location."),
-                            }
+                    let to = blizzard
+                        .step(pos, &inside_walls)
+                        .expect("Toroidal positions always resolve");
+                    match &mut new_map[to.0.map(|x| usize::try_from(x).unwrap())] {
+                        x @ Cell::Floor => *x = Cell::Blizzards(vec![*blizzard]),
+                        Cell::Blizzards(b) => b.push(*blizzard),
+                        Cell::Wall => unreachable!(
+                            "No walls within the box. Blizzard should wrap to a non-wall location."
+                        ),
                     }
                 }
             }
@@ -198,7 +181,10 @@ mod parse {
     use crate::data::{Cell, Map};
     use advent_lib::{
         dir::Dir,
-        parse::yap::{all_consuming, line_ending, AllConsuming},
+        parse::{
+            yap::{all_consuming, line_ending},
+            ParseError,
+        },
     };
     use ndarray::ArrayBase;
     use yap::{IntoTokens, Tokens};
@@ -232,7 +218,7 @@ mod parse {
                     break;
                 }
             }
-            if line_ending(input).is_some() {
+            if line_ending(input).is_ok() {
                 cols += 1;
             } else {
                 break;
@@ -243,7 +229,7 @@ mod parse {
             .reversed_axes() // make x axis first number and y axis second number
     }
 
-    pub fn parse_input(input: &str) -> Result<Map, AllConsuming<String>> {
+    pub fn parse_input(input: &str) -> Result<Map, ParseError> {
         all_consuming(&mut input.into_tokens(), initial_map)
     }
 }
@@ -289,11 +275,11 @@ mod part1 {
         data::{end, start},
         parse::parse_input,
     };
-    use advent_lib::parse::read_and_leak;
+    use advent_lib::parse::Input;
 
     pub fn run(file_name: &str) -> Result<usize, Box<dyn Error>> {
-        let input = read_and_leak(file_name)?;
-        let map = parse_input(input)?;
+        let input = Input::read(file_name)?;
+        let map = parse_input(&input)?;
         let (start, end) = (start(&map).expect("start"), end(&map).expect("end"));
         Ok(shared::shortest_dist(&map, start, end).0)
     }
@@ -305,11 +291,11 @@ mod part2 {
         data::{end, start},
         parse::parse_input,
     };
-    use advent_lib::parse::read_and_leak;
+    use advent_lib::parse::Input;
 
     pub fn run(file_name: &str) -> Result<usize, Box<dyn Error>> {
-        let input = read_and_leak(file_name)?;
-        let map = parse_input(input)?;
+        let input = Input::read(file_name)?;
+        let map = parse_input(&input)?;
         let (start, end) = (start(&map).expect("start"), end(&map).expect("end"));
         let (dist_to_end, map) = shared::shortest_dist(&map, start, end);
         let (dist_back_to_start, map) = shared::shortest_dist(&map, end, start);
@@ -154,11 +154,11 @@ mod part1 {
         data::{SnafuNum, Val},
         parse::parse_input,
     };
-    use advent_lib::parse::read_and_leak;
+    use advent_lib::parse::Input;
 
     pub fn run(file_name: &str) -> Result<String, Box<dyn Error>> {
-        let input = read_and_leak(file_name)?;
-        let nums = parse_input(input)?;
+        let input = Input::read(file_name)?;
+        let nums = parse_input(&input)?;
         Ok(SnafuNum::from(nums.into_iter().map(Val::from).sum::<Val>()).to_string())
     }
 }
@@ -62,18 +62,13 @@ In how many assignment pairs do the ranges overlap?
 
 */
 
-use lazy_static::lazy_static;
-use regex::Regex;
+use advent_lib::scan;
 use std::{
     error::Error,
     fs::File,
     io::{BufRead, BufReader},
 };
 
-lazy_static! {
-    static ref LINE_REGEX: Regex = Regex::new(r"(\d+)-(\d+),(\d+)-(\d+)").unwrap();
-}
-
 fn main() -> Result<(), Box<dyn Error>> {
     part1::run()?;
     part2::run()?;
@@ -90,20 +85,12 @@ mod part1 {
         for line in reader.lines() {
             let line = line?;
 
-            for cap in LINE_REGEX.captures_iter(&line) {
-                let lower_1 =
-                    u32::from_str_radix(cap.get(1).map(|m| m.as_str()).unwrap(), 10).unwrap();
-                let upper_1 =
-                    u32::from_str_radix(cap.get(2).map(|m| m.as_str()).unwrap(), 10).unwrap();
-                let lower_2 =
-                    u32::from_str_radix(cap.get(3).map(|m| m.as_str()).unwrap(), 10).unwrap();
-                let upper_2 =
-                    u32::from_str_radix(cap.get(4).map(|m| m.as_str()).unwrap(), 10).unwrap();
-                if (lower_1 <= lower_2 && upper_1 >= upper_2)
-                    || (lower_2 <= lower_1 && upper_2 >= upper_1)
-                {
-                    result += 1;
-                }
+            let (lower_1, upper_1, lower_2, upper_2): (u32, u32, u32, u32) =
+                scan!(line, "{}-{},{}-{}")?;
+            if (lower_1 <= lower_2 && upper_1 >= upper_2)
+                || (lower_2 <= lower_1 && upper_2 >= upper_1)
+            {
+                result += 1;
             }
         }
 
@@ -122,20 +109,12 @@ mod part2 {
         for line in reader.lines() {
             let line = line?;
 
-            for cap in LINE_REGEX.captures_iter(&line) {
-                let lower_1 =
-                    u32::from_str_radix(cap.get(1).map(|m| m.as_str()).unwrap(), 10).unwrap();
-                let upper_1 =
-                    u32::from_str_radix(cap.get(2).map(|m| m.as_str()).unwrap(), 10).unwrap();
-                let lower_2 =
-                    u32::from_str_radix(cap.get(3).map(|m| m.as_str()).unwrap(), 10).unwrap();
-                let upper_2 =
-                    u32::from_str_radix(cap.get(4).map(|m| m.as_str()).unwrap(), 10).unwrap();
-                if (upper_1 >= lower_2 && lower_1 <= upper_2)
-                    || (upper_2 >= lower_1 && lower_2 <= upper_1)
-                {
-                    result += 1;
-                }
+            let (lower_1, upper_1, lower_2, upper_2): (u32, u32, u32, u32) =
+                scan!(line, "{}-{},{}-{}")?;
+            if (upper_1 >= lower_2 && lower_1 <= upper_2)
+                || (upper_2 >= lower_1 && lower_2 <= upper_1)
+            {
+                result += 1;
             }
         }
 
@@ -111,18 +111,12 @@ Before the rearrangement process finishes, update your simulation so that the El
 
 */
 
-use itertools::Itertools;
-use lazy_static::lazy_static;
-use regex::Regex;
-use std::{
-    collections::VecDeque,
-    error::Error,
-    fs::File,
-    io::{BufRead, BufReader, Lines},
+use advent_lib::{
+    parse::{sections, Input},
+    scan,
 };
-lazy_static! {
-    static ref ACTION_PARSE_REGEX: Regex = Regex::new(r"move (\d+) from (\d+) to (\d+)").unwrap();
-}
+use itertools::Itertools;
+use std::{collections::VecDeque, error::Error};
 
 fn main() -> Result<(), Box<dyn Error>> {
     part1::run()?;
@@ -133,23 +127,8 @@ fn main() -> Result<(), Box<dyn Error>> {
 mod part1 {
     use super::*;
     pub fn run() -> Result<(), Box<dyn Error>> {
-        let f = File::open("input.txt")?;
-        let reader = BufReader::new(f);
-        let mut actions = vec![];
-
-        let mut lines = reader.lines();
-        let mut state = parse_initial_state(&mut lines)?;
-        for line in lines {
-            let line = line?;
-            let line = line.to_string();
-            let nums = ACTION_PARSE_REGEX.captures_iter(&line).nth(0).unwrap();
-            let nums = vec![
-                usize::from_str_radix(&nums[1], 10).unwrap(),
-                usize::from_str_radix(&nums[2], 10).unwrap(),
-                usize::from_str_radix(&nums[3], 10).unwrap(),
-            ];
-            actions.push(nums);
-        }
+        let input = Input::read("input.txt")?;
+        let (mut state, actions) = parse_input(&input)?;
 
         for action in actions {
             for _amount in 0..action[0] {
@@ -173,23 +152,8 @@ mod part1 {
 mod part2 {
     use super::*;
     pub fn run() -> Result<(), Box<dyn Error>> {
-        let f = File::open("input.txt")?;
-        let reader = BufReader::new(f);
-        let mut actions = vec![];
-
-        let mut lines = reader.lines();
-        let mut state = parse_initial_state(&mut lines)?;
-        for line in lines {
-            let line = line?;
-            let line = line.to_string();
-            let nums = ACTION_PARSE_REGEX.captures_iter(&line).nth(0).unwrap();
-            let nums = vec![
-                usize::from_str_radix(&nums[1], 10).unwrap(),
-                usize::from_str_radix(&nums[2], 10).unwrap(),
-                usize::from_str_radix(&nums[3], 10).unwrap(),
-            ];
-            actions.push(nums);
-        }
+        let input = Input::read("input.txt")?;
+        let (mut state, actions) = parse_input(&input)?;
 
         for action in actions {
             let mut moved_crates = Vec::new();
@@ -215,19 +179,30 @@ mod part2 {
     }
 }
 
-fn parse_initial_state(
-    lines: &mut Lines<BufReader<File>>,
-) -> Result<Vec<VecDeque<char>>, Box<dyn Error>> {
+type Stacks = Vec<VecDeque<char>>;
+type Actions = Vec<Vec<usize>>;
+
+fn parse_input(input: &str) -> Result<(Stacks, Actions), Box<dyn Error>> {
+    let mut sections = sections(input);
+    let (Some(drawing), Some(moves)) = (sections.next(), sections.next()) else {
+        return Err("expected a crate drawing and moves separated by a blank line".into());
+    };
+    let state = parse_initial_state(drawing.text);
+    let mut actions = vec![];
+    for line in moves.text.lines() {
+        let (amount, from, to) = scan!(line, "move {} from {} to {}")?;
+        let nums = vec![amount, from, to];
+        actions.push(nums);
+    }
+    Ok((state, actions))
+}
+
+fn parse_initial_state(drawing: &str) -> Stacks {
     let mut state = vec![];
     let mut num_crate_cols;
     let mut first = true;
 
-    while let Some(line) = lines.next() {
-        let line = line?;
-
-        if line.trim().is_empty() {
-            break;
-        }
+    for line in drawing.lines() {
         // Stuff to do on element. (Identify shape)
         if first {
             // Since the last element has 3 instead of 4 chars must add 1 to compensate.
@@ -250,5 +225,5 @@ fn parse_initial_state(
             });
     }
 
-    Ok(state)
+    state
 }
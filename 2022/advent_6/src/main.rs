@@ -34,6 +34,7 @@ How many characters need to be processed before the first start-of-packet marker
 
  */
 
+use advent_lib::iters::first_distinct_window;
 use std::{error::Error, fs::File, io::{BufReader, Read}};
 
 fn main() -> Result<(), Box<dyn Error>> {
@@ -85,21 +86,5 @@ mod part2 {
 }
 
 fn find_nonduplicate_index(buffer: &[u8], windows_size: usize) -> Option<usize> {
-    buffer.windows(windows_size).position(|w| {
-        let mut non_overlapping = 0u32;
-        // mod 26 because there are 26 letters that must be distinguished
-        // Could improve by using custom iterator instead of `window` and skipping by the lower index of the duplicate instead of checking windows which still contains the duplicate offender.
-        for character in w {
-            let new = 1 << (character % 26) as u32;
-            non_overlapping ^= new;
-
-            // DEBUG
-            // println!("{new:#032b}");
-            // println!("{non_overlapping:#032b}");
-            // println!();
-        }
-
-        // This will only be true if there were no overlaps.
-        u32::count_ones(non_overlapping) == windows_size as u32
-    })
+    first_distinct_window(buffer, windows_size)
 }
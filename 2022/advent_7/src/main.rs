@@ -1,3 +1,4 @@
+use advent_lib::{parse::read_lines, scan};
 use std::{
     cmp::Reverse,
     collections::{BTreeMap, BinaryHeap},
@@ -75,15 +76,16 @@ mod part2 {
 }
 
 fn parse(
-    reader: BufReader<File>,
+    reader: impl BufRead,
     current_dir: &mut Vec<String>,
     filesystem_state: &mut BTreeMap<String, Vec<FileSystemObj>>,
 ) -> Result<(), Box<dyn Error>> {
-    let mut lines = reader.lines().peekable();
-    while let Some(Ok(line)) = lines.next() {
-        match &line[0..1] {
-            "$" => match &line[2..4] {
-                "cd" => match &line[5..] {
+    let mut lines = read_lines(reader).peekable();
+    while let Some(line) = lines.next() {
+        let line = line?;
+        match &line.text[0..1] {
+            "$" => match &line.text[2..4] {
+                "cd" => match &line.text[5..] {
                     ".." => {
                         current_dir.pop();
                     }
@@ -93,57 +95,28 @@ fn parse(
                 },
                 "ls" => {
                     while let Some(Ok(line2)) = lines.peek() {
-                        if &line2[0..1] == "$" {
+                        if &line2.text[0..1] == "$" {
                             break;
                         }
                         let line = lines.next().unwrap()?;
-                        let split_line: Vec<_> = line.split_ascii_whitespace().collect();
-                        match split_line[0] {
-                            "dir" => match filesystem_state.get_mut(
-                                &current_dir
+                        let obj = match scan!(line.text, "dir {}") {
+                            Ok(name) => FileSystemObj::Dir(name),
+                            Err(_) => {
+                                let (size, name) =
+                                    scan!(line.text, "{u32} {}").map_err(|e| line.locate(e))?;
+                                FileSystemObj::File(name, size)
+                            }
+                        };
+                        filesystem_state
+                            .entry(
+                                current_dir
                                     .iter()
                                     .cloned()
                                     .map(|s| String::from(s + "/"))
                                     .collect::<String>(),
-                            ) {
-                                Some(x) => x.push(FileSystemObj::Dir(split_line[1].to_string())),
-                                None => {
-                                    filesystem_state.insert(
-                                        current_dir
-                                            .iter()
-                                            .cloned()
-                                            .map(|s| String::from(s + "/"))
-                                            .collect::<String>(),
-                                        vec![FileSystemObj::Dir(split_line[1].to_string())],
-                                    );
-                                }
-                            },
-                            size => match filesystem_state.get_mut(
-                                &current_dir
-                                    .iter()
-                                    .cloned()
-                                    .map(|s| String::from(s + "/"))
-                                    .collect::<String>(),
-                            ) {
-                                Some(x) => x.push(FileSystemObj::File(
-                                    split_line[1].to_string(),
-                                    u32::from_str_radix(size, 10).unwrap(),
-                                )),
-                                None => {
-                                    filesystem_state.insert(
-                                        current_dir
-                                            .iter()
-                                            .cloned()
-                                            .map(|s| String::from(s + "/"))
-                                            .collect::<String>(),
-                                        vec![FileSystemObj::File(
-                                            split_line[1].to_string(),
-                                            u32::from_str_radix(size, 10).unwrap(),
-                                        )],
-                                    );
-                                }
-                            },
-                        }
+                            )
+                            .or_default()
+                            .push(obj);
                     }
                 }
                 x => panic!("{x} is invalid command!"),
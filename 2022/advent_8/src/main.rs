@@ -1,3 +1,4 @@
+use advent_lib::parse::{read_lines, ParseError};
 use ndarray::Array2;
 use std::{
     error::Error,
@@ -46,24 +47,23 @@ mod part2 {
     }
 }
 
-fn parse(reader: BufReader<File>) -> Result<Array2<u8>, Box<dyn Error>> {
+fn parse(reader: impl BufRead) -> Result<Array2<u8>, Box<dyn Error>> {
     let mut data = Vec::new();
     let mut nrow = 0;
     let mut ncol = 0;
-    for line in reader.lines() {
+    for line in read_lines(reader) {
         let line = line?;
         nrow += 1;
-        for c in line.chars() {
-            data.push(c);
+        for (i, c) in line.text.char_indices() {
+            let height = c
+                .to_digit(10)
+                .ok_or_else(|| line.locate(ParseError::new(&line.text, i, "digit")))?;
+            data.push(u8::try_from(height)?);
             if nrow == 1 {
                 ncol += 1;
             }
         }
     }
-    let data: Vec<_> = data
-        .iter()
-        .map(|c| u8::try_from(c.to_digit(10).unwrap()).unwrap())
-        .collect();
     let output = Array2::from_shape_vec((nrow, ncol), data)?;
 
     Ok(output)
@@ -1,57 +1,14 @@
+use advent_lib::cord::NDCord;
 use itertools::Itertools;
 use std::{
     collections::HashSet,
     error::Error,
     fs::File,
     io::{BufRead, BufReader},
-    ops::{Add, AddAssign, Div, Sub},
     rc::Rc, cell::RefCell
 };
 
-#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-struct Pos(isize, isize);
-
-impl Pos {
-    fn op1(self, f: fn(isize) -> isize) -> Self {
-        Pos(f(self.0), f(self.1))
-    }
-    fn op2<T: Into<isize>>(self, rhs: Self, f: fn(isize, isize) -> T) -> Self {
-        Pos(f(self.0, rhs.0).into(), f(self.1, rhs.1).into())
-    }
-    fn op2_refutable<T: Into<Option<isize>>>(
-        self,
-        rhs: Self,
-        f: fn(isize, isize) -> T,
-    ) -> Option<Self> {
-        let x = f(self.0, rhs.0).into()?;
-        let y = f(self.1, rhs.1).into()?;
-        Some(Pos(x, y))
-    }
-    fn manhattan_distance(self, other: &Self) -> u32 {
-        let temp = self.op2(*other, isize::sub).op1(isize::abs);
-        (temp.0 + temp.1).try_into().unwrap()
-    }
-}
-
-impl Add<Self> for Pos {
-    type Output = Self;
-    fn add(self, rhs: Self) -> Self::Output {
-        self.op2(rhs, isize::add)
-    }
-}
-
-impl AddAssign<Self> for Pos {
-    fn add_assign(&mut self, other: Self) {
-        *self = *self + other;
-    }
-}
-
-impl Sub<Self> for Pos {
-    type Output = Self;
-    fn sub(self, rhs: Self) -> Self::Output {
-        self.op2(rhs, isize::sub)
-    }
-}
+type Pos = NDCord<isize, 2>;
 
 fn main() -> Result<(), Box<dyn Error>> {
     println!("Part 1 answer: {}", part1::run("input.txt")?);
@@ -65,8 +22,8 @@ mod part1 {
     pub fn run(filename: &str) -> Result<u32, Box<dyn Error>> {
         let f = File::open(filename)?;
         let reader = BufReader::new(f);
-        let mut hpos = Pos(0, 0);
-        let mut tpos = Pos(0, 0);
+        let mut hpos = Pos::default();
+        let mut tpos = Pos::default();
         let mut visited_positions = HashSet::new();
 
         for line in reader.lines() {
@@ -74,10 +31,10 @@ mod part1 {
 
             for _ in 0..u32::from_str_radix(&line[2..], 10).unwrap() {
                 hpos = match &line[0..1] {
-                    "U" => hpos + Pos(0, 1),
-                    "D" => hpos + Pos(0, -1),
-                    "L" => hpos + Pos(-1, 0),
-                    "R" => hpos + Pos(1, 0),
+                    "U" => hpos + Pos::new([0, 1]),
+                    "D" => hpos + Pos::new([0, -1]),
+                    "L" => hpos + Pos::new([-1, 0]),
+                    "R" => hpos + Pos::new([1, 0]),
                     _ => unimplemented!(),
                 };
 
@@ -106,10 +63,10 @@ mod part2 {
             for _ in 0..u32::from_str_radix(&line[2..], 10).unwrap() {
                 // move the rope head
                 knots[0] = match &line[0..1] {
-                    "U" => knots[0] + Pos(0, 1),
-                    "D" => knots[0] + Pos(0, -1),
-                    "L" => knots[0] + Pos(-1, 0),
-                    "R" => knots[0] + Pos(1, 0),
+                    "U" => knots[0] + Pos::new([0, 1]),
+                    "D" => knots[0] + Pos::new([0, -1]),
+                    "L" => knots[0] + Pos::new([-1, 0]),
+                    "R" => knots[0] + Pos::new([1, 0]),
                     _ => unimplemented!(),
                 };
                 // update tails
@@ -127,22 +84,9 @@ mod part2 {
 }
 
 fn update_tail(tpos: &mut Pos, hpos: &Pos) {
-    // Row/Column lagging
-    if hpos.0 == tpos.0 && hpos.1.abs_diff(tpos.1) == 2 {
-        let dif = hpos.1 - tpos.1;
-        tpos.1 += dif.div(dif.abs())
-    } else if hpos.1 == tpos.1 && hpos.0.abs_diff(tpos.0) == 2 {
-        let dif = hpos.0 - tpos.0;
-        tpos.0 += dif.div(dif.abs())
-    }
-    // Diagonal lagging
-    // ex (2,1) - (1,0) = (1,0)
-    else if hpos.manhattan_distance(tpos) >= 3 {
-        let dif = *hpos - *tpos;
-        // Manhattan distance is 3 when diagonal needs updating.
-            if let Some(x) = dif.op2_refutable(dif.op1(isize::abs), isize::checked_div) {
-                *tpos += x;
-            }
+    // Lagging once the head isn't touching the tail. Move one step towards the head, diagonally if not in the same row/column.
+    if hpos.chebyshev_distance(tpos) >= 2 {
+        *tpos += (*hpos - *tpos).signum();
     }
 }
 
@@ -156,16 +100,16 @@ mod tests {
     }
 
     #[test]
-    fn manhattan_distance_test() {
-        let a = Pos(1, 0);
-        let b = Pos(2, 1);
-        assert_eq!(a.manhattan_distance(&b), 2);
-        let a = Pos(1, 0);
-        let b = Pos(2, 2);
-        assert_eq!(a.manhattan_distance(&b), 3);
-        let a = Pos(2, -3);
-        let b = Pos(0, 0);
-        assert_eq!(a.manhattan_distance(&b), 5);
+    fn update_tail_test() {
+        let mut tail = Pos::new([1, 0]);
+        update_tail(&mut tail, &Pos::new([2, 1]));
+        assert_eq!(tail, Pos::new([1, 0]));
+        update_tail(&mut tail, &Pos::new([3, 0]));
+        assert_eq!(tail, Pos::new([2, 0]));
+        update_tail(&mut tail, &Pos::new([3, 2]));
+        assert_eq!(tail, Pos::new([3, 1]));
+        update_tail(&mut tail, &Pos::new([5, 3]));
+        assert_eq!(tail, Pos::new([4, 2]));
     }
 
     #[test]
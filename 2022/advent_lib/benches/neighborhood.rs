@@ -0,0 +1,66 @@
+use advent_lib::{cord::NDCord, iters::NDCartesianProduct};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use num_iter::range_inclusive;
+use std::array;
+
+/// Radius 1 moore neighborhood built like it was before the offset tables, from a product of ranges.
+fn moore_product<const DIM: usize>(
+    cord: NDCord<i32, DIM>,
+) -> impl Iterator<Item = NDCord<i32, DIM>> {
+    NDCartesianProduct::new(array::from_fn(|_| range_inclusive(0, 2)))
+        .map(NDCord)
+        .map(move |offset| NDCord(cord.0.map(|x| x - 1)) + offset)
+        .filter(move |x| *x != cord)
+}
+
+fn neumann_product<const DIM: usize>(
+    cord: NDCord<i32, DIM>,
+) -> impl Iterator<Item = NDCord<i32, DIM>> {
+    moore_product(cord).filter(move |x| x.manhattan_distance(&cord) <= 1)
+}
+
+/// Consume with internal iteration like `sum` or `count` would.
+fn fold_sum<const DIM: usize>(neighbors: impl Iterator<Item = NDCord<i32, DIM>>) -> i32 {
+    neighbors.fold(0, |acc, x| acc + x[0])
+}
+
+/// Consume one item at a time like a `for` loop or `any` would.
+fn next_sum<const DIM: usize>(neighbors: impl Iterator<Item = NDCord<i32, DIM>>) -> i32 {
+    let mut acc = 0;
+    for x in neighbors {
+        acc += x[0];
+    }
+    acc
+}
+
+fn bench_dim<const DIM: usize>(c: &mut Criterion) {
+    let cord = NDCord::<i32, DIM>(array::from_fn(|i| i as i32 * 7 - 3));
+    let mut group = c.benchmark_group(format!("radius 1 in {DIM}D"));
+    group.bench_function("moore table fold", |b| {
+        b.iter(|| fold_sum(black_box(cord).moore_neighborhood(1)))
+    });
+    group.bench_function("moore product fold", |b| {
+        b.iter(|| fold_sum(moore_product(black_box(cord))))
+    });
+    group.bench_function("moore table next", |b| {
+        b.iter(|| next_sum(black_box(cord).moore_neighborhood(1)))
+    });
+    group.bench_function("moore product next", |b| {
+        b.iter(|| next_sum(moore_product(black_box(cord))))
+    });
+    group.bench_function("neumann table", |b| {
+        b.iter(|| fold_sum(black_box(cord).neumann_neighborhood(1)))
+    });
+    group.bench_function("neumann product", |b| {
+        b.iter(|| fold_sum(neumann_product(black_box(cord))))
+    });
+    group.finish();
+}
+
+fn neighborhood(c: &mut Criterion) {
+    bench_dim::<2>(c);
+    bench_dim::<3>(c);
+}
+
+criterion_group!(benches, neighborhood);
+criterion_main!(benches);
@@ -1,15 +1,22 @@
-use crate::iters::NDCartesianProduct;
+use crate::{iters::NDCartesianProduct, offsets, topology};
 use derive_more::{Deref, DerefMut};
-use num_iter::range_inclusive;
-use num_traits::{cast, NumCast, One, ToPrimitive, Zero};
+use itertools::{Either, Itertools};
+use num_traits::{
+    cast, CheckedAdd, CheckedSub, Euclid, NumCast, PrimInt, Signed, ToPrimitive, Zero,
+};
 use std::{
     array,
     clone::Clone,
-    cmp::PartialEq,
+    cmp::{Ordering, PartialEq},
     fmt::{Debug, Display},
     iter::{Iterator, Sum},
+    marker::PhantomData,
     num::NonZeroUsize,
-    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign},
+    ops::{
+        Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, RangeInclusive, Rem, RemAssign, Sub,
+        SubAssign,
+    },
+    str::FromStr,
 };
 
 pub fn abs_diff<T: Sub<Output = T> + PartialOrd>(x: T, y: T) -> T {
@@ -29,7 +36,7 @@ pub struct NDCord<T, const DIM: usize>(pub [T; DIM]);
 impl<T: Display, const DIM: usize> Display for NDCord<T, DIM> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "[")?;
-        if self.len() > 0 {
+        if !self.is_empty() {
             for e in &self.0[..self.0.len() - 1] {
                 write!(f, "{}, ", e)?;
             }
@@ -41,6 +48,56 @@ impl<T: Display, const DIM: usize> Display for NDCord<T, DIM> {
     }
 }
 
+/// Error from parsing an [`NDCord`] with [`str::parse`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ParseCordError<E> {
+    #[error("Expected {expected} comma separated values but found {found}")]
+    WrongDimension { expected: usize, found: usize },
+    #[error("Can't parse axis {axis}: {source}")]
+    Axis {
+        axis: usize,
+        #[source]
+        source: E,
+    },
+}
+
+impl<T: FromStr, const DIM: usize> FromStr for NDCord<T, DIM> {
+    type Err = ParseCordError<T::Err>;
+
+    /// Parse comma separated values like `x,y` or `x, y, z` with any whitespace around each value.
+    /// Values are parsed with `T`'s [`FromStr`] so signs like `-1` or `+1` work if `T` accepts them.
+    /// The brackets that [`Display`] puts around the values are also allowed.
+    /// ```
+    /// # use advent_lib::cord::NDCord;
+    /// assert_eq!("498,4".parse(), Ok(NDCord([498, 4])));
+    /// assert_eq!(" -2, +18 ,0".parse(), Ok(NDCord([-2, 18, 0])));
+    /// assert_eq!("[1, 2]".parse(), Ok(NDCord([1, 2])));
+    /// assert!("1,2".parse::<NDCord<i32, 3>>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let s = s
+            .strip_prefix('[')
+            .and_then(|s| s.strip_suffix(']'))
+            .unwrap_or(s);
+        let found = s.split(',').count();
+        if found != DIM {
+            return Err(ParseCordError::WrongDimension {
+                expected: DIM,
+                found,
+            });
+        }
+        let mut values = s.split(',');
+        NDCord::try_from_fn(|axis| {
+            let value = values.next().expect("Checked the number of values");
+            value
+                .trim()
+                .parse()
+                .map_err(|source| ParseCordError::Axis { axis, source })
+        })
+    }
+}
+
 impl<T: Default + Copy, const DIM: usize> Default for NDCord<T, DIM> {
     fn default() -> Self {
         Self([Default::default(); DIM])
@@ -127,55 +184,253 @@ where
     }
 }
 
+impl<T, const DIM: usize> Rem<T> for NDCord<T, DIM>
+where
+    T: Rem<Output = T> + Clone,
+{
+    type Output = Self;
+
+    fn rem(self, rhs: T) -> Self::Output {
+        array::from_fn(|i| self[i].clone() % rhs.clone()).into()
+    }
+}
+
+impl<T, const DIM: usize> RemAssign<T> for NDCord<T, DIM>
+where
+    T: Rem<Output = T> + Clone,
+{
+    fn rem_assign(&mut self, rhs: T) {
+        *self = self.clone() % rhs
+    }
+}
+
+impl<T, const DIM: usize> Neg for NDCord<T, DIM>
+where
+    T: Neg<Output = T>,
+{
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        NDCord(self.0.map(T::neg))
+    }
+}
+
 /// Iterator over the moore neighborhood centered at some cord.
 #[must_use = "iterators are lazy and do nothing unless consumed"]
 #[derive(Clone, Debug)]
 pub struct MooreNeighborhoodIterator<I, T, const DIM: usize> {
     iterator: I,
     cord: NDCord<T, DIM>,
-    radius: usize,
+    remaining: usize,
+}
+
+/// Inclusive minimum and maximum offset on each axis from a cord, saturated to fit an [`i128`].
+type WideBox<const DIM: usize> = ([i128; DIM], [i128; DIM]);
+
+/// Values from `start` to `end` (inclusive) as an axis for [`NDCartesianProduct`] that knows its length.
+fn wide_axis(
+    start: i128,
+    end: i128,
+) -> impl ExactSizeIterator<Item = i128> + DoubleEndedIterator + Clone {
+    let len = if start > end {
+        0
+    } else {
+        usize::try_from(end.abs_diff(start))
+            .ok()
+            .and_then(|x| x.checked_add(1))
+            .expect("Axis should have at most usize::MAX values")
+    };
+    (0..len).map(move |x| start + x as i128)
 }
 
 impl<I, T, const DIM: usize> Iterator for MooreNeighborhoodIterator<I, T, DIM>
 where
     I: Iterator<Item = NDCord<T, DIM>>,
-    T: Add<Output = T> + Sub<Output = T> + PartialEq + Clone + NumCast,
+    T: PartialEq,
 {
     type Item = NDCord<T, DIM>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        // Each radius increases number of cells in each dimension by 2 (each extent direction by 1) starting with 1 cell at radius = 1.
-        while let Some(cord_offset) = self.iterator.next() {
-            let smallest_neighbor = NDCord(self.cord.0.clone().map(|x| {
-                x - cast(self.radius).expect("Can't cast the radius to cord's datatype.")
-            }));
-            let new_cord = smallest_neighbor + cord_offset;
-
+        for new_cord in self.iterator.by_ref() {
             // Don't add self to neighbor list.
             if new_cord == self.cord {
                 continue;
             }
 
+            self.remaining -= 1;
             return Some(new_cord);
         }
         None
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let sidelength = self.radius + 1 + self.radius;
-        let volume = (0..DIM).map(|_| sidelength).product::<usize>();
-        // Area or Volume minus the cell the neighborhood is for.
-        (volume - 1, Some(volume - 1))
+        (self.remaining, Some(self.remaining))
+    }
+
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        let cord = self.cord;
+        self.iterator
+            .fold(init, |acc, x| if x == cord { acc } else { f(acc, x) })
     }
 }
 
 impl<I, T, const DIM: usize> ExactSizeIterator for MooreNeighborhoodIterator<I, T, DIM>
 where
     I: Iterator<Item = NDCord<T, DIM>>,
-    T: Add<Output = T> + Sub<Output = T> + PartialEq + Clone + NumCast,
+    T: PartialEq,
 {
 }
 
+/// Iterator over the cells on a straight line between two cords. See [`NDCord::line_to`].
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[derive(Clone, Debug)]
+pub struct LineIterator<T, const DIM: usize> {
+    start: [i128; DIM],
+    delta: [i128; DIM],
+    /// Number of steps along the longest axis.
+    length: i128,
+    front: i128,
+    /// One past the last step that hasn't been yielded.
+    back: i128,
+    _marker: PhantomData<T>,
+}
+
+impl<T: NumCast, const DIM: usize> LineIterator<T, DIM> {
+    /// Cell `step` steps along the longest axis. Other axes are rounded to the nearest cell.
+    fn cell(&self, step: i128) -> NDCord<T, DIM> {
+        NDCord(array::from_fn(|axis| {
+            let offset = if self.length == 0 {
+                0
+            } else {
+                (2 * self.delta[axis] * step + self.length).div_euclid(2 * self.length)
+            };
+            cast(self.start[axis] + offset).expect("Line cell fits in cord's datatype")
+        }))
+    }
+}
+
+impl<T: NumCast, const DIM: usize> Iterator for LineIterator<T, DIM> {
+    type Item = NDCord<T, DIM>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front >= self.back {
+            return None;
+        }
+        self.front += 1;
+        Some(self.cell(self.front - 1))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = usize::try_from(self.back - self.front).unwrap_or(0);
+        (len, Some(len))
+    }
+}
+
+impl<T: NumCast, const DIM: usize> DoubleEndedIterator for LineIterator<T, DIM> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front >= self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(self.cell(self.back))
+    }
+}
+
+impl<T: NumCast, const DIM: usize> ExactSizeIterator for LineIterator<T, DIM> {}
+
+/// Cells along the line segments joining each point to the next, like the `->` separated paths in day 14.
+/// Each joint is only emitted once.
+/// ```
+/// # use advent_lib::cord::{polyline, NDCord};
+/// let path = [NDCord([498, 4]), NDCord([498, 6]), NDCord([496, 6])];
+/// assert_eq!(
+///     polyline(path).collect::<Vec<_>>(),
+///     [[498, 4], [498, 5], [498, 6], [497, 6], [496, 6]].map(NDCord)
+/// );
+/// ```
+pub fn polyline<T, const DIM: usize>(
+    points: impl IntoIterator<Item = NDCord<T, DIM>>,
+) -> impl Iterator<Item = NDCord<T, DIM>>
+where
+    T: NumCast + Clone,
+{
+    let mut points = points.into_iter().peekable();
+    let first = points.peek().cloned();
+    first.into_iter().chain(
+        points
+            .tuple_windows()
+            .flat_map(|(from, to)| from.line_to(&to).skip(1)),
+    )
+}
+
+fn gcd(mut a: i128, mut b: i128) -> i128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a.abs()
+}
+
+/// Edges of the closed path through `vertices` as `(from, to)` pairs in `i128` so products can't overflow.
+fn closed_edges(vertices: &[NDCord<i64, 2>]) -> impl Iterator<Item = ([i128; 2], [i128; 2])> + '_ {
+    let widen = |x: &NDCord<i64, 2>| x.0.map(<i128 as From<i64>>::from);
+    vertices
+        .iter()
+        .map(widen)
+        .zip(vertices.iter().cycle().skip(1).map(widen))
+}
+
+/// Twice the area enclosed by the closed path through `vertices` using the shoelace formula.
+///
+/// Doubled so the result stays an integer. The path closes from the last vertex back to the first and may go in either direction.
+/// Vertices can be just the corners or every cell along the path.
+/// ```
+/// # use advent_lib::cord::{shoelace_area_doubled, NDCord};
+/// let triangle = [[0, 0], [3, 0], [0, 3]].map(NDCord);
+/// assert_eq!(shoelace_area_doubled(&triangle), 9);
+/// ```
+pub fn shoelace_area_doubled(vertices: &[NDCord<i64, 2>]) -> i64 {
+    let doubled: i128 = closed_edges(vertices)
+        .map(|(a, b)| a[0] * b[1] - b[0] * a[1])
+        .sum();
+    doubled.abs().try_into().expect("Area fits in i64")
+}
+
+/// Number of lattice points on the closed path through `vertices`. Equal to its length when every edge is axis aligned.
+pub fn boundary_length(vertices: &[NDCord<i64, 2>]) -> i64 {
+    let length: i128 = closed_edges(vertices)
+        .map(|(a, b)| gcd(b[0] - a[0], b[1] - a[1]))
+        .sum();
+    length.try_into().expect("Length fits in i64")
+}
+
+/// Number of lattice points strictly inside the closed path through `vertices` using Pick's theorem.
+/// The path shouldn't cross itself or enclose no area.
+pub fn interior_points(vertices: &[NDCord<i64, 2>]) -> i64 {
+    // A = I + B/2 - 1
+    (shoelace_area_doubled(vertices) - boundary_length(vertices) + 2) / 2
+}
+
+/// Number of lattice points inside or on the closed path through `vertices`.
+/// This is the number of cells filled in when the path is traced through cell centers and the inside is filled in.
+/// ```
+/// # use advent_lib::cord::{enclosed_points, NDCord};
+/// // #####
+/// // #...#
+/// // #####
+/// let rectangle = [[0, 0], [4, 0], [4, 2], [0, 2]].map(NDCord);
+/// assert_eq!(enclosed_points(&rectangle), 15);
+/// ```
+pub fn enclosed_points(vertices: &[NDCord<i64, 2>]) -> i64 {
+    if vertices.is_empty() {
+        return 0;
+    }
+    // I + B = A + B/2 + 1, which also holds for paths that enclose no area.
+    (shoelace_area_doubled(vertices) + boundary_length(vertices)) / 2 + 1
+}
+
 impl<T, const DIM: usize> NDCord<T, DIM> {
     pub fn apply<O>(self, other: Self, func: impl Fn(T, T) -> O) -> NDCord<O, DIM> {
         let mut other = other.0.into_iter();
@@ -192,56 +447,576 @@ impl<T, const DIM: usize> NDCord<T, DIM> {
         diff_per_axis.0.into_iter().sum()
     }
 
+    /// Largest difference on any axis. Number of king moves between the cords.
+    pub fn chebyshev_distance(self, other: &Self) -> T
+    where
+        T: Sub<Output = T> + Ord + Clone + Zero,
+    {
+        let diff_per_axis = self.apply(other.clone(), abs_diff::<T>);
+        diff_per_axis.0.into_iter().fold(T::zero(), T::max)
+    }
+
+    /// Square of the straight line distance. Stays exact for integers where the distance itself wouldn't.
+    pub fn euclidean_distance_squared(self, other: &Self) -> T
+    where
+        T: Sub<Output = T> + Mul<Output = T> + Sum + PartialOrd + Clone,
+    {
+        let diff_per_axis = self.apply(other.clone(), abs_diff::<T>);
+        diff_per_axis.0.into_iter().map(|x| x.clone() * x).sum()
+    }
+
+    pub fn dot(self, other: &Self) -> T
+    where
+        T: Mul<Output = T> + Sum + Clone,
+    {
+        self.apply(other.clone(), T::mul).0.into_iter().sum()
+    }
+
+    pub fn abs(&self) -> Self
+    where
+        T: Signed,
+    {
+        NDCord(self.0.each_ref().map(T::abs))
+    }
+
+    /// Sign of each axis. Gives a king move towards another cord with `(other - self).signum()`.
+    pub fn signum(&self) -> Self
+    where
+        T: Signed,
+    {
+        NDCord(self.0.each_ref().map(T::signum))
+    }
+
+    /// [`Euclid::rem_euclid`] on each axis with that axis' modulus. Wraps a cord into the box from zero to `modulus` (exclusive).
+    /// ```
+    /// # use advent_lib::cord::NDCord;
+    /// assert_eq!(NDCord([-1, 7]).rem_euclid(&NDCord([5, 5])), NDCord([4, 2]));
+    /// ```
+    pub fn rem_euclid(&self, modulus: &Self) -> Self
+    where
+        T: Euclid,
+    {
+        NDCord(array::from_fn(|i| self[i].rem_euclid(&modulus[i])))
+    }
+
+    /// Smallest value on each axis. Unlike [`Ord::min`] which compares whole cords lexicographically.
+    pub fn component_min(self, other: Self) -> Self
+    where
+        T: Ord,
+    {
+        self.apply(other, T::min)
+    }
+
+    /// Largest value on each axis. Unlike [`Ord::max`] which compares whole cords lexicographically.
+    pub fn component_max(self, other: Self) -> Self
+    where
+        T: Ord,
+    {
+        self.apply(other, T::max)
+    }
+
+    /// Clamp each axis between that axis of `min` and `max`.
+    pub fn component_clamp(self, min: Self, max: Self) -> Self
+    where
+        T: Ord,
+    {
+        self.component_max(min).component_min(max)
+    }
+
     /// Radius is manhattan distance from center to edge.
     /// Moore neighborhood is a square formed by the extents of the Neumann neighborhood.
+    ///
+    /// Neighbors that don't fit in `T` are skipped so unsigned grid indices can be used directly.
+    /// ```
+    /// # use advent_lib::cord::NDCord;
+    /// let corner: Vec<_> = NDCord([0usize, 0]).moore_neighborhood(1).collect();
+    /// assert_eq!(corner, [[0, 1], [1, 0], [1, 1]].map(NDCord));
+    /// ```
+    ///
+    /// Only integer cords have neighborhoods. Float cords used to have them with every value truncated, so that's no longer allowed.
+    /// ```compile_fail
+    /// # use advent_lib::cord::NDCord;
+    /// NDCord([0.5f64, 0.5]).moore_neighborhood(1);
+    /// ```
     pub fn moore_neighborhood(
         &self,
         radius: usize,
     ) -> MooreNeighborhoodIterator<impl Iterator<Item = NDCord<T, DIM>> + Clone, T, DIM>
     where
-        T: Add<Output = T> + PartialOrd + Clone + NumCast + Zero + One,
+        T: PrimInt,
     {
-        let dim_max = cast::<usize, T>(radius + radius)
-            .expect("Can't convert radius + radius into cord's datatype.");
+        self.moore_neighborhood_clipped(radius, self.type_box())
+    }
 
-        let iterator = NDCartesianProduct::new(array::from_fn(|_| {
-            range_inclusive(Zero::zero(), dim_max.clone())
-        }))
-        .map(NDCord);
+    /// [`NDCord::moore_neighborhood`] with only the neighbors inside `bounds`.
+    /// ```
+    /// # use advent_lib::{cord::NDCord, topology::Bounded};
+    /// let grid = Bounded::with_size(NDCord([3usize, 2]));
+    /// let neighbors: Vec<_> = NDCord([2, 1]).moore_neighborhood_in(1, &grid).collect();
+    /// assert_eq!(neighbors, [[1, 0], [1, 1], [2, 0]].map(NDCord));
+    /// ```
+    pub fn moore_neighborhood_in(
+        &self,
+        radius: usize,
+        bounds: &topology::Bounded<T, DIM>,
+    ) -> MooreNeighborhoodIterator<impl Iterator<Item = NDCord<T, DIM>> + Clone, T, DIM>
+    where
+        T: PrimInt,
+    {
+        self.moore_neighborhood_clipped(radius, self.bounds_box(bounds))
+    }
+
+    fn moore_neighborhood_clipped(
+        &self,
+        radius: usize,
+        clip: WideBox<DIM>,
+    ) -> MooreNeighborhoodIterator<impl Iterator<Item = NDCord<T, DIM>> + Clone, T, DIM>
+    where
+        T: PrimInt,
+    {
+        let (origin, center) = (*self, [0; DIM]);
+        let contains_self = (0..DIM).all(|i| clip.0[i] <= center[i] && center[i] <= clip.1[i]);
+
+        let (iterator, volume) = match offsets::moore(DIM).filter(|_| radius == 1) {
+            Some(offsets) => {
+                let values = self.adjacent_values(&clip);
+                let volume = values
+                    .iter()
+                    .map(|x| x.iter().flatten().count())
+                    .product::<usize>();
+                (Either::Left(Self::from_offsets(offsets, values)), volume)
+            }
+            None => {
+                let product = NDCartesianProduct::new(Self::clip_axes(
+                    Self::wide_moore_box(&center, radius),
+                    &clip,
+                ));
+                let volume = product.len();
+                let product = product.map(move |x| origin.at_offset(x));
+                (Either::Right(product), volume)
+            }
+        };
 
         MooreNeighborhoodIterator {
             iterator,
-            cord: self.clone(),
-            radius,
+            cord: origin,
+            // Area or Volume minus the cell the neighborhood is for.
+            remaining: volume - contains_self as usize,
         }
     }
 
     /// Radius is manhattan distance of furthest neighbors.
     /// Neumann neighborhood is all cells a manhattan distance of the radius or smaller.
-    pub fn neumann_neighborhood(&self, radius: usize) -> impl Iterator<Item = NDCord<T, DIM>> + '_
+    ///
+    /// Like [`NDCord::moore_neighborhood`], neighbors that don't fit in `T` are skipped.
+    pub fn neumann_neighborhood(&self, radius: usize) -> impl Iterator<Item = NDCord<T, DIM>>
     where
-        T: Sub<Output = T> + Sum + PartialOrd + Clone + NumCast + Zero + One,
+        T: PrimInt,
     {
-        let neighbors = self.moore_neighborhood(radius);
-        neighbors.filter(move |x| {
-            x.clone().manhattan_distance(self)
-                <= cast(radius).expect("Can't convert radius to cord's datatype.")
+        self.neumann_neighborhood_clipped(radius, self.type_box())
+    }
+
+    /// [`NDCord::neumann_neighborhood`] with only the neighbors inside `bounds`.
+    pub fn neumann_neighborhood_in(
+        &self,
+        radius: usize,
+        bounds: &topology::Bounded<T, DIM>,
+    ) -> impl Iterator<Item = NDCord<T, DIM>>
+    where
+        T: PrimInt,
+    {
+        self.neumann_neighborhood_clipped(radius, self.bounds_box(bounds))
+    }
+
+    fn neumann_neighborhood_clipped(
+        &self,
+        radius: usize,
+        clip: WideBox<DIM>,
+    ) -> impl Iterator<Item = NDCord<T, DIM>>
+    where
+        T: PrimInt,
+    {
+        let (origin, center) = (*self, [0; DIM]);
+        if let Some(offsets) = offsets::neumann(DIM).filter(|_| radius == 1) {
+            let values = self.adjacent_values(&clip);
+            return Either::Left(Self::from_offsets(offsets, values));
+        }
+        let iterator = NDCartesianProduct::new(Self::clip_axes(
+            Self::wide_moore_box(&center, radius),
+            &clip,
+        ))
+        .filter(move |x| {
+            let distance = (0..DIM).map(|i| x[i].abs_diff(center[i])).sum::<u128>();
+            distance != 0 && distance <= radius as u128
         })
+        .map(move |x| origin.at_offset(x));
+        Either::Right(iterator)
+    }
+
+    /// Value one below, at, and one above `self` on each axis if it's inside `clip`.
+    fn adjacent_values(&self, clip: &WideBox<DIM>) -> [[Option<T>; 3]; DIM]
+    where
+        T: PrimInt,
+    {
+        array::from_fn(|i| {
+            array::from_fn(|offset| {
+                let offset = offset as i128 - 1;
+                (clip.0[i] <= offset && offset <= clip.1[i]).then(|| self.axis_at(i, offset))
+            })
+        })
+    }
+
+    /// Cords at each offset from an [`offsets`] table picked out of `values`, skipping any with a value that's out of range.
+    fn from_offsets(
+        offsets: &'static [i8],
+        values: [[Option<T>; 3]; DIM],
+    ) -> impl Iterator<Item = NDCord<T, DIM>> + Clone
+    where
+        T: Clone,
+    {
+        offsets.chunks_exact(DIM).filter_map(move |offset| {
+            let value = |i: usize| &values[i][(offset[i] + 1) as usize];
+            (0..DIM).all(|i| value(i).is_some()).then(|| {
+                NDCord(array::from_fn(|i| {
+                    value(i).clone().expect("Checked each axis is in range")
+                }))
+            })
+        })
+    }
+
+    /// Cells at a chebyshev distance of exactly `radius`, the outer edge of [`NDCord::moore_neighborhood`].
+    /// A radius of 0 is only the cord itself.
+    /// ```
+    /// # use advent_lib::cord::NDCord;
+    /// assert_eq!(NDCord([0, 0]).moore_ring(2).count(), 16);
+    /// assert_eq!(NDCord([0, 0, 0]).moore_ring(1).count(), 26);
+    /// ```
+    pub fn moore_ring(&self, radius: usize) -> impl Iterator<Item = NDCord<T, DIM>>
+    where
+        T: PrimInt,
+    {
+        self.moore_ring_clipped(radius, self.type_box())
+    }
+
+    /// [`NDCord::moore_ring`] with only the cells inside `bounds`.
+    pub fn moore_ring_in(
+        &self,
+        radius: usize,
+        bounds: &topology::Bounded<T, DIM>,
+    ) -> impl Iterator<Item = NDCord<T, DIM>>
+    where
+        T: PrimInt,
+    {
+        self.moore_ring_clipped(radius, self.bounds_box(bounds))
+    }
+
+    fn moore_ring_clipped(
+        &self,
+        radius: usize,
+        clip: WideBox<DIM>,
+    ) -> impl Iterator<Item = NDCord<T, DIM>>
+    where
+        T: PrimInt,
+    {
+        let (origin, center) = (*self, [0; DIM]);
+        let full = Self::wide_moore_box(&center, radius);
+        let r = radius as i128;
+        let in_clip = move |i: usize, x: &i128| clip.0[i] <= *x && *x <= clip.1[i];
+        // Each cell comes from the first axis it is on the edge of.
+        // Axes before that one are strictly inside the edges and axes after it can be anything in range.
+        (0..DIM)
+            .flat_map(move |edge_axis| {
+                let axes: [_; DIM] = array::from_fn(|i| {
+                    let (start, end) = (full[i].start(), full[i].end());
+                    match i.cmp(&edge_axis) {
+                        Ordering::Less => Either::Left(start + 1..=end - 1),
+                        Ordering::Equal => {
+                            let low = center[i].checked_sub(r);
+                            let high = center[i].checked_add(r).filter(|_| r != 0);
+                            let (mut values, mut len) = ([0; 2], 0);
+                            for x in low.into_iter().chain(high).filter(|x| in_clip(i, x)) {
+                                values[len] = x;
+                                len += 1;
+                            }
+                            Either::Right(values.into_iter().take(len))
+                        }
+                        Ordering::Greater => Either::Left(*start..=*end),
+                    }
+                    .map_left(|axis| {
+                        wide_axis(*axis.start().max(&clip.0[i]), *axis.end().min(&clip.1[i]))
+                    })
+                });
+                NDCartesianProduct::new(axes)
+            })
+            .map(move |x| origin.at_offset(x))
+    }
+
+    /// Cells at a manhattan distance of exactly `radius`, the outer edge of [`NDCord::neumann_neighborhood`].
+    /// A radius of 0 is only the cord itself.
+    /// ```
+    /// # use advent_lib::cord::NDCord;
+    /// let ring: Vec<_> = NDCord([0, 0]).neumann_ring(1).collect();
+    /// assert_eq!(ring, [[-1, 0], [0, -1], [0, 1], [1, 0]].map(NDCord));
+    /// ```
+    pub fn neumann_ring(&self, radius: usize) -> impl Iterator<Item = NDCord<T, DIM>>
+    where
+        T: PrimInt,
+    {
+        self.neumann_ring_clipped(radius, self.type_box())
+    }
+
+    /// [`NDCord::neumann_ring`] with only the cells inside `bounds`.
+    pub fn neumann_ring_in(
+        &self,
+        radius: usize,
+        bounds: &topology::Bounded<T, DIM>,
+    ) -> impl Iterator<Item = NDCord<T, DIM>>
+    where
+        T: PrimInt,
+    {
+        self.neumann_ring_clipped(radius, self.bounds_box(bounds))
+    }
+
+    fn neumann_ring_clipped(
+        &self,
+        radius: usize,
+        clip: WideBox<DIM>,
+    ) -> impl Iterator<Item = NDCord<T, DIM>>
+    where
+        T: PrimInt,
+    {
+        let (origin, center) = (*self, [0; DIM]);
+        let last = DIM.saturating_sub(1);
+        let mut full = Self::wide_moore_box(&center, radius);
+        // Every axis but the last can be anything in range and the last axis makes up the rest of the distance.
+        if let Some(axis) = full.last_mut() {
+            *axis = center[last]..=center[last];
+        }
+        let (min, max) = (clip.0[last], clip.1[last]);
+        let mut last_clip = clip;
+        if let (Some(low), Some(high)) = (last_clip.0.last_mut(), last_clip.1.last_mut()) {
+            (*low, *high) = (i128::MIN, i128::MAX);
+        }
+        // With no axes there's no last axis to make up the distance with.
+        NDCartesianProduct::new(Self::clip_axes(full, &last_clip))
+            .filter(|_| DIM > 0)
+            .flat_map(move |x| {
+                let used = (0..last).map(|i| x[i].abs_diff(center[i])).sum::<u128>();
+                let rest = (radius as u128).checked_sub(used).map(|rest| rest as i128);
+                let low = rest.and_then(|rest| center[last].checked_sub(rest));
+                let high = rest
+                    .filter(|&rest| rest != 0)
+                    .and_then(|rest| center[last].checked_add(rest));
+                low.into_iter()
+                    .chain(high)
+                    .filter(move |value| min <= *value && *value <= max)
+                    .map(move |value| {
+                        let mut x = x;
+                        x[last] = value;
+                        x
+                    })
+            })
+            .map(move |x| origin.at_offset(x))
+    }
+
+    /// Range of each axis within `radius` of `center`.
+    fn wide_moore_box(center: &[i128; DIM], radius: usize) -> [RangeInclusive<i128>; DIM] {
+        let radius = radius as i128;
+        center.map(|x| x.saturating_sub(radius)..=x.saturating_add(radius))
+    }
+
+    /// Each axis limited to `clip`.
+    fn clip_axes(
+        axes: [RangeInclusive<i128>; DIM],
+        clip: &WideBox<DIM>,
+    ) -> [impl ExactSizeIterator<Item = i128> + DoubleEndedIterator + Clone; DIM] {
+        array::from_fn(|i| {
+            wide_axis(
+                *axes[i].start().max(&clip.0[i]),
+                *axes[i].end().min(&clip.1[i]),
+            )
+        })
+    }
+
+    /// Offsets to every value `T` can hold.
+    fn type_box(&self) -> WideBox<DIM>
+    where
+        T: PrimInt,
+    {
+        self.offsets_box(&[T::min_value(); DIM], &[T::max_value(); DIM])
+    }
+
+    fn bounds_box(&self, bounds: &topology::Bounded<T, DIM>) -> WideBox<DIM>
+    where
+        T: PrimInt,
+    {
+        self.offsets_box(&bounds.min, &bounds.max)
+    }
+
+    fn offsets_box(&self, min: &[T; DIM], max: &[T; DIM]) -> WideBox<DIM>
+    where
+        T: PrimInt,
+    {
+        (
+            array::from_fn(|i| Self::axis_offset(self[i], min[i])),
+            array::from_fn(|i| Self::axis_offset(self[i], max[i])),
+        )
+    }
+
+    /// How far `to` is from `from`, saturated to fit an [`i128`].
+    /// Neighbors are never that far away, so saturating doesn't change which ones are in range.
+    fn axis_offset(from: T, to: T) -> i128
+    where
+        T: PrimInt,
+    {
+        let wide = |x: T| x.to_i128();
+        if let Some(offset) = wide(from)
+            .zip(wide(to))
+            .and_then(|(from, to)| to.checked_sub(from))
+        {
+            return offset;
+        }
+        // Only `u128` values too big for an i128 and offsets across most of the `i128` range get here.
+        if to >= from {
+            to.checked_sub(&from)
+                .and_then(|x| x.to_i128())
+                .unwrap_or(i128::MAX)
+        } else {
+            from.checked_sub(&to)
+                .and_then(|x| x.to_i128())
+                .map_or(i128::MIN, |x| -x)
+        }
+    }
+
+    /// The value `offset` away from `self` on `axis`, which must fit in `T`.
+    fn axis_at(&self, axis: usize, offset: i128) -> T
+    where
+        T: PrimInt,
+    {
+        let value = self[axis];
+        value
+            .to_i128()
+            .and_then(|x| x.checked_add(offset))
+            .and_then(cast)
+            .or_else(|| {
+                // `u128` is the only type that gets here and it holds the whole offset.
+                let distance = T::from(offset.unsigned_abs())?;
+                match offset < 0 {
+                    true => value.checked_sub(&distance),
+                    false => value.checked_add(&distance),
+                }
+            })
+            .expect("Offset was clipped to fit")
+    }
+
+    fn at_offset(&self, offset: [i128; DIM]) -> Self
+    where
+        T: PrimInt,
+    {
+        NDCord(array::from_fn(|i| self.axis_at(i, offset[i])))
+    }
+
+    /// Convert each axis to another numeric type without losing anything. [`None`] if any axis doesn't fit,
+    /// including floats with a fractional part and integers too precise for a float.
+    /// ```
+    /// # use advent_lib::cord::NDCord;
+    /// assert_eq!(NDCord([1i64, 2]).try_cast::<usize>(), Some(NDCord([1, 2])));
+    /// assert_eq!(NDCord([1i64, -2]).try_cast::<usize>(), None);
+    /// assert_eq!(NDCord([0.5, 2.0]).try_cast::<i32>(), None);
+    /// ```
+    pub fn try_cast<U: NumCast + Clone>(&self) -> Option<NDCord<U, DIM>>
+    where
+        T: NumCast + PartialEq + Clone,
+    {
+        NDCord::try_from_fn(|i| {
+            let value = U::from(self[i].clone()).ok_or(())?;
+            // Casting truncates floats and rounds big integers to floats, which shows up when casting back.
+            match T::from(value.clone()) == Some(self[i].clone()) {
+                true => Ok(value),
+                false => Err(()),
+            }
+        })
+        .ok()
+    }
+
+    /// Add each axis. [`None`] if any axis overflows.
+    pub fn checked_add(&self, other: &Self) -> Option<Self>
+    where
+        T: CheckedAdd,
+    {
+        self.checked_apply(other, T::checked_add)
+    }
+
+    /// Subtract each axis. [`None`] if any axis overflows, like an unsigned cord going below zero.
+    /// ```
+    /// # use advent_lib::cord::NDCord;
+    /// assert_eq!(NDCord([3usize, 1]).checked_sub(&NDCord([1, 1])), Some(NDCord([2, 0])));
+    /// assert_eq!(NDCord([3usize, 1]).checked_sub(&NDCord([0, 2])), None);
+    /// ```
+    pub fn checked_sub(&self, other: &Self) -> Option<Self>
+    where
+        T: CheckedSub,
+    {
+        self.checked_apply(other, T::checked_sub)
+    }
+
+    fn checked_apply(&self, other: &Self, f: impl Fn(&T, &T) -> Option<T>) -> Option<Self> {
+        Self::try_from_fn(|i| f(&self[i], &other[i]).ok_or(())).ok()
+    }
+
+    /// Build a cord from each axis' index, stopping at the first error.
+    fn try_from_fn<E>(mut f: impl FnMut(usize) -> Result<T, E>) -> Result<Self, E> {
+        let mut out = Vec::with_capacity(DIM);
+        for i in 0..DIM {
+            out.push(f(i)?);
+        }
+        Ok(NDCord(
+            out.try_into()
+                .unwrap_or_else(|_| unreachable!("One value per axis")),
+        ))
+    }
+
+    /// Cells on the straight line from `self` to `other` (both inclusive) using Bresenham's algorithm generalized to N dimensions.
+    ///
+    /// Moves exactly one cell along the axis with the largest difference per step, so diagonals and lines at any angle have no gaps.
+    /// Each other axis is rounded to the nearest cell, with halfway points rounded up.
+    /// ```
+    /// # use advent_lib::cord::NDCord;
+    /// let line: Vec<_> = NDCord([0, 0]).line_to(&NDCord([4, -2])).collect();
+    /// assert_eq!(line, [[0, 0], [1, 0], [2, -1], [3, -1], [4, -2]].map(NDCord));
+    /// ```
+    pub fn line_to(&self, other: &Self) -> LineIterator<T, DIM>
+    where
+        T: ToPrimitive,
+    {
+        let to_i128 = |x: &T| x.to_i128().expect("Cord fits in i128");
+        let start = self.0.each_ref().map(to_i128);
+        let end = other.0.each_ref().map(to_i128);
+        let delta = array::from_fn(|axis| end[axis] - start[axis]);
+        let length = delta.iter().map(|x| x.abs()).max().unwrap_or(0);
+        LineIterator {
+            start,
+            delta,
+            length,
+            front: 0,
+            back: length + 1,
+            _marker: PhantomData,
+        }
     }
 
     /// Return an iterator over all points (inclusive) between `self` and `other`. Order is lexicographical.
     pub fn interpolate(&self, other: &Self) -> impl Iterator<Item = NDCord<T, DIM>>
     where
-        T: Add<Output = T> + Ord + Clone + One + ToPrimitive,
+        T: NumCast + Ord + Clone,
     {
+        let to_i128 = |x: &T| x.to_i128().expect("Cord fits in i128");
         // Use min and max so range doesn't silently emit no values (high..low is length 0 range)
-        let ranges = array::from_fn(|i| {
-            range_inclusive(
-                self.0[i].clone().min(other.0[i].clone()),
-                self.0[i].clone().max(other.0[i].clone()),
-            )
+        let axes: [_; DIM] = array::from_fn(|i| {
+            let (low, high) = (&self.0[i], &other.0[i]);
+            wide_axis(to_i128(low.min(high)), to_i128(low.max(high)))
+                .map(|x| cast(x).expect("Value is between two cords"))
         });
-        NDCartesianProduct::new(ranges).map(NDCord)
+        NDCartesianProduct::new(axes).map(NDCord)
     }
 
     /// Finds the largest value in each dimension and smallest value in each dimension as the pair `(min, max)`.
@@ -301,6 +1076,45 @@ impl<T, const DIM: usize> NDCord<T, DIM> {
     }
 }
 
+impl<T: Clone> NDCord<T, 2> {
+    pub fn x(&self) -> T {
+        self[0].clone()
+    }
+
+    pub fn y(&self) -> T {
+        self[1].clone()
+    }
+}
+
+impl<T: Clone> NDCord<T, 3> {
+    pub fn x(&self) -> T {
+        self[0].clone()
+    }
+
+    pub fn y(&self) -> T {
+        self[1].clone()
+    }
+
+    pub fn z(&self) -> T {
+        self[2].clone()
+    }
+}
+
+impl<T> NDCord<T, 3> {
+    /// Vector perpendicular to both with the right hand rule.
+    pub fn cross(&self, other: &Self) -> Self
+    where
+        T: Mul<Output = T> + Sub<Output = T> + Clone,
+    {
+        let [a, b] = [self, other].map(|x| x.0.clone());
+        NDCord([
+            a[1].clone() * b[2].clone() - a[2].clone() * b[1].clone(),
+            a[2].clone() * b[0].clone() - a[0].clone() * b[2].clone(),
+            a[0].clone() * b[1].clone() - a[1].clone() * b[0].clone(),
+        ])
+    }
+}
+
 impl<T, const DIM: usize> From<[T; DIM]> for NDCord<T, DIM> {
     fn from(value: [T; DIM]) -> Self {
         NDCord(value)
@@ -320,9 +1134,55 @@ impl<T, const DIM: usize> From<NDCord<T, DIM>> for [T; DIM] {
     }
 }
 
+/// Cords serialize as a sequence of their values, like the array they wrap.
+#[cfg(feature = "serde")]
+mod serde_impls {
+    use super::NDCord;
+    use serde::{
+        de::{Error, SeqAccess, Visitor},
+        ser::SerializeTuple,
+        Deserialize, Deserializer, Serialize, Serializer,
+    };
+    use std::{fmt, marker::PhantomData};
+
+    impl<T: Serialize, const DIM: usize> Serialize for NDCord<T, DIM> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut tuple = serializer.serialize_tuple(DIM)?;
+            for x in &self.0 {
+                tuple.serialize_element(x)?;
+            }
+            tuple.end()
+        }
+    }
+
+    struct CordVisitor<T, const DIM: usize>(PhantomData<T>);
+
+    impl<'de, T: Deserialize<'de>, const DIM: usize> Visitor<'de> for CordVisitor<T, DIM> {
+        type Value = NDCord<T, DIM>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            write!(formatter, "a sequence of {DIM} values")
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            NDCord::try_from_fn(|axis| {
+                seq.next_element()?
+                    .ok_or_else(|| A::Error::invalid_length(axis, &self))
+            })
+        }
+    }
+
+    impl<'de, T: Deserialize<'de>, const DIM: usize> Deserialize<'de> for NDCord<T, DIM> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserializer.deserialize_tuple(DIM, CordVisitor(PhantomData))
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::topology::Bounded;
 
     #[test]
     fn manhattan_distance_test() {
@@ -451,6 +1311,212 @@ mod tests {
         );
     }
 
+    #[test]
+    fn neighborhood_type_edge_test() {
+        // Neighbors below zero or past the max are skipped instead of wrapping or panicking.
+        let out = NDCord([0u8, 255]).moore_neighborhood(1);
+        assert_eq!(out.len(), 3);
+        assert_eq!(
+            out.collect::<Vec<_>>(),
+            vec![NDCord([0, 254]), NDCord([1, 254]), NDCord([1, 255])]
+        );
+        assert_eq!(
+            NDCord([0usize, 1])
+                .neumann_neighborhood(1)
+                .collect::<Vec<_>>(),
+            vec![NDCord([0, 0]), NDCord([0, 2]), NDCord([1, 1])]
+        );
+        // A radius too large for the type is clipped to the whole range.
+        assert_eq!(NDCord([3i8]).moore_neighborhood(1000).len(), 255);
+        assert_eq!(NDCord([3u8]).neumann_neighborhood(300).count(), 255);
+        let mut out = NDCord([0u16, 0, 0]).moore_neighborhood(2);
+        out.next();
+        assert_eq!(out.size_hint(), (25, Some(25)));
+        // Values that don't fit in an i128 still have neighbors.
+        let edge = u128::MAX;
+        assert_eq!(
+            NDCord([edge, 0]).moore_neighborhood(1).collect::<Vec<_>>(),
+            vec![
+                NDCord([edge - 1, 0]),
+                NDCord([edge - 1, 1]),
+                NDCord([edge, 1])
+            ]
+        );
+        let middle = i128::MAX as u128;
+        assert_eq!(
+            NDCord([middle]).neumann_ring(1).collect::<Vec<_>>(),
+            vec![NDCord([middle - 1]), NDCord([middle + 1])]
+        );
+        let bounds = Bounded::new(NDCord([edge - 1]), NDCord([edge]));
+        assert_eq!(NDCord([edge]).moore_ring_in(2, &bounds).count(), 0);
+        assert_eq!(NDCord([i128::MIN, i128::MAX]).moore_ring(1).count(), 3);
+    }
+
+    #[test]
+    fn bounded_neighborhood_test() {
+        let bounds = Bounded::new(NDCord([0, -1, 0]), NDCord([4, 1, 0]));
+        for cord in [NDCord([0, 0, 0]), NDCord([4, 1, 0]), NDCord([2, 5, 0])] {
+            for radius in 0..4 {
+                let expected: Vec<_> = cord
+                    .moore_neighborhood(radius)
+                    .filter(|x| bounds.contains(x))
+                    .collect();
+                let out = cord.moore_neighborhood_in(radius, &bounds);
+                assert_eq!(out.len(), expected.len());
+                assert_eq!(out.collect::<Vec<_>>(), expected);
+                assert_eq!(
+                    cord.neumann_neighborhood_in(radius, &bounds)
+                        .collect::<Vec<_>>(),
+                    cord.neumann_neighborhood(radius)
+                        .filter(|x| bounds.contains(x))
+                        .collect::<Vec<_>>()
+                );
+            }
+        }
+        let grid = Bounded::with_size(NDCord([2usize, 1]));
+        assert_eq!(
+            NDCord([1, 0])
+                .neumann_neighborhood_in(1, &grid)
+                .collect::<Vec<_>>(),
+            vec![NDCord([0, 0])]
+        );
+    }
+
+    /// Radius 1 neighborhoods from the offset tables match the general neighborhoods cut down to radius 1.
+    fn check_radius_one<const DIM: usize>(cord: NDCord<u8, DIM>, bounds: &Bounded<u8, DIM>) {
+        let near = |x: &NDCord<u8, DIM>| x.chebyshev_distance(&cord) <= 1;
+        let moore = cord.moore_neighborhood(1);
+        let expected: Vec<_> = cord.moore_neighborhood(2).filter(near).collect();
+        assert_eq!(moore.len(), expected.len());
+        assert_eq!(moore.collect::<Vec<_>>(), expected);
+        let moore = cord.moore_neighborhood_in(1, bounds);
+        let expected: Vec<_> = cord.moore_neighborhood_in(2, bounds).filter(near).collect();
+        assert_eq!(moore.len(), expected.len());
+        assert_eq!(moore.collect::<Vec<_>>(), expected);
+
+        let near = |x: &NDCord<u8, DIM>| x.manhattan_distance(&cord) <= 1;
+        assert!(cord
+            .neumann_neighborhood(1)
+            .eq(cord.neumann_neighborhood(2).filter(near)));
+        assert!(cord
+            .neumann_neighborhood_in(1, bounds)
+            .eq(cord.neumann_neighborhood_in(2, bounds).filter(near)));
+    }
+
+    #[test]
+    fn radius_one_test() {
+        check_radius_one(NDCord([0]), &Bounded::new(NDCord([0]), NDCord([0])));
+        check_radius_one(NDCord([5, 255]), &Bounded::with_size(NDCord([6, 6])));
+        check_radius_one(NDCord([1, 0, 9]), &Bounded::with_size(NDCord([2, 2, 2])));
+        check_radius_one(
+            NDCord([1, 1, 1, 1]),
+            &Bounded::with_size(NDCord([3, 1, 3, 2])),
+        );
+        // Past the largest table.
+        check_radius_one(
+            NDCord([1, 0, 1, 255, 1]),
+            &Bounded::with_size(NDCord([9; 5])),
+        );
+    }
+
+    #[test]
+    fn ring_test() {
+        // Rings are the cells of the neighborhood at exactly the radius, in the same order.
+        let bounds = Bounded::new(NDCord([-2, 0, -3]), NDCord([1, 2, 3]));
+        for cord in [NDCord([0, 0, 0]), NDCord([-1, 2, 3]), NDCord([5, 5, 5])] {
+            for radius in 1..5 {
+                let mut moore: Vec<_> = cord
+                    .moore_neighborhood(radius)
+                    .filter(|x| x.chebyshev_distance(&cord) == radius as i32)
+                    .collect();
+                let mut out: Vec<_> = cord.moore_ring(radius).collect();
+                moore.sort();
+                out.sort();
+                assert_eq!(out, moore);
+                let mut out: Vec<_> = cord.moore_ring_in(radius, &bounds).collect();
+                out.sort();
+                moore.retain(|x| bounds.contains(x));
+                assert_eq!(out, moore);
+
+                let neumann: Vec<_> = cord
+                    .neumann_neighborhood(radius)
+                    .filter(|x| x.manhattan_distance(&cord) == radius as i32)
+                    .collect();
+                assert_eq!(cord.neumann_ring(radius).collect::<Vec<_>>(), neumann);
+                assert!(cord
+                    .neumann_ring_in(radius, &bounds)
+                    .eq(neumann.into_iter().filter(|x| bounds.contains(x))));
+            }
+            assert_eq!(cord.moore_ring(0).collect::<Vec<_>>(), vec![cord]);
+            assert_eq!(cord.neumann_ring(0).collect::<Vec<_>>(), vec![cord]);
+        }
+        assert_eq!(
+            NDCord([0u8]).neumann_ring(3).collect::<Vec<_>>(),
+            vec![NDCord([3])]
+        );
+        assert_eq!(NDCord([1u8, 1]).moore_ring(1).count(), 8);
+        assert_eq!(NDCord([0u8, 0]).moore_ring(1).count(), 3);
+    }
+
+    #[test]
+    fn from_str_test() {
+        assert_eq!("1,2,3".parse(), Ok(NDCord([1u8, 2, 3])));
+        assert_eq!("\t-7 ,  0\n".parse(), Ok(NDCord([-7, 0])));
+        assert_eq!("+5".parse(), Ok(NDCord([5usize])));
+        assert_eq!(
+            "1,2,3".parse::<NDCord<i32, 2>>(),
+            Err(ParseCordError::WrongDimension {
+                expected: 2,
+                found: 3
+            })
+        );
+        assert!(matches!(
+            "1,,3".parse::<NDCord<i32, 3>>(),
+            Err(ParseCordError::Axis { axis: 1, .. })
+        ));
+        assert!(matches!(
+            "0,-1".parse::<NDCord<u32, 2>>(),
+            Err(ParseCordError::Axis { axis: 1, .. })
+        ));
+        let cord = NDCord([-3, 40, 2]);
+        assert_eq!(cord.to_string().parse(), Ok(cord));
+        assert_eq!((cord.x(), cord.y(), cord.z()), (-3, 40, 2));
+        assert_eq!(NDCord([4, 5]).y(), 5);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_test() {
+        let cord = NDCord([1, -2, 3]);
+        let json = serde_json::to_string(&cord).unwrap();
+        assert_eq!(json, "[1,-2,3]");
+        assert_eq!(serde_json::from_str::<NDCord<i32, 3>>(&json).unwrap(), cord);
+        assert!(serde_json::from_str::<NDCord<i32, 3>>("[1,2]").is_err());
+        assert!(serde_json::from_str::<NDCord<i32, 3>>("[1,2,3,4]").is_err());
+    }
+
+    #[test]
+    fn checked_test() {
+        let cord = NDCord([2usize, 5]);
+        assert_eq!(cord.checked_sub(&NDCord([2, 1])), Some(NDCord([0, 4])));
+        assert_eq!(cord.checked_sub(&NDCord([3, 1])), None);
+        assert_eq!(
+            cord.checked_add(&NDCord([usize::MAX - 2, 0])),
+            Some(NDCord([usize::MAX, 5]))
+        );
+        assert_eq!(cord.checked_add(&NDCord([0, usize::MAX])), None);
+        assert_eq!(cord.try_cast::<i8>(), Some(NDCord([2, 5])));
+        assert_eq!(NDCord([300, 0]).try_cast::<u8>(), None);
+        assert_eq!(NDCord([-1.5, 2.0]).try_cast::<i32>(), None);
+        assert_eq!(NDCord([-1.0, 2.0]).try_cast::<i32>(), Some(NDCord([-1, 2])));
+        assert_eq!(
+            NDCord([1i64 << 24, 0]).try_cast::<f32>(),
+            Some(NDCord([16777216.0, 0.0]))
+        );
+        assert_eq!(NDCord([(1i64 << 24) + 1, 0]).try_cast::<f32>(), None);
+        assert_eq!(NDCord([0.1f64]).try_cast::<f32>(), None);
+    }
+
     #[test]
     fn interpolate_test() {
         let cord1 = NDCord([498, 4]);
@@ -515,4 +1581,136 @@ mod tests {
             assert_eq!(NDCord::from_offset(3, widths), NDCord([0, 1, 1]));
         }
     }
+
+    #[test]
+    fn line_to_test() {
+        // Axis aligned lines are the same as interpolating.
+        let (a, b) = (NDCord([3, 7]), NDCord([3, 2]));
+        assert_eq!(
+            a.line_to(&b).rev().collect::<Vec<_>>(),
+            a.interpolate(&b).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            NDCord([1u8, 1])
+                .line_to(&NDCord([4, 4]))
+                .collect::<Vec<_>>(),
+            [[1, 1], [2, 2], [3, 3], [4, 4]].map(NDCord)
+        );
+        assert_eq!(
+            NDCord([0, 0]).line_to(&NDCord([2, 5])).collect::<Vec<_>>(),
+            [[0, 0], [0, 1], [1, 2], [1, 3], [2, 4], [2, 5]].map(NDCord)
+        );
+        assert_eq!(
+            NDCord([5, 5]).line_to(&NDCord([5, 5])).collect::<Vec<_>>(),
+            vec![NDCord([5, 5])]
+        );
+
+        // Every step moves by at most 1 on each axis and ends on the endpoint.
+        let (a, b) = (NDCord([-3i64, 10, 2]), NDCord([7, -1, 5]));
+        let line = a.line_to(&b);
+        assert_eq!(line.len(), 12);
+        let line: Vec<_> = line.collect();
+        assert_eq!((line[0], line[11]), (a, b));
+        for pair in line.windows(2) {
+            assert!(pair[0]
+                .apply(pair[1], |x, y| (x - y).abs())
+                .iter()
+                .all(|&x| x <= 1));
+            assert_eq!(pair[1][1] - pair[0][1], -1);
+        }
+    }
+
+    #[test]
+    fn polyline_test() {
+        let points = [[0, 0], [2, 0], [2, 2], [0, 0]].map(NDCord::<i32, 2>);
+        assert_eq!(
+            polyline(points).collect::<Vec<_>>(),
+            [[0, 0], [1, 0], [2, 0], [2, 1], [2, 2], [1, 1], [0, 0]].map(NDCord)
+        );
+        assert_eq!(polyline([NDCord([1, 2])]).count(), 1);
+        assert_eq!(polyline(Vec::<NDCord<i32, 2>>::new()).count(), 0);
+    }
+
+    #[test]
+    fn polygon_test() {
+        let square = [[0, 0], [4, 0], [4, 3], [0, 3]].map(NDCord);
+        assert_eq!(shoelace_area_doubled(&square), 24);
+        assert_eq!(boundary_length(&square), 14);
+        assert_eq!(interior_points(&square), 6);
+        assert_eq!(enclosed_points(&square), 20);
+
+        // Same answers going the other way around or listing every cell on the path.
+        let mut reversed = square;
+        reversed.reverse();
+        assert_eq!(interior_points(&reversed), 6);
+        let cells: Vec<_> = polyline(square.into_iter().chain([square[0]])).collect();
+        assert_eq!(shoelace_area_doubled(&cells), 24);
+        assert_eq!(boundary_length(&cells), 14);
+
+        // Diagonal edges only pass through some lattice points.
+        let triangle = [[0, 0], [4, 0], [0, 2]].map(NDCord);
+        assert_eq!(boundary_length(&triangle), 8);
+        assert_eq!(interior_points(&triangle), 1);
+
+        // U shape with a notch cut out of the top.
+        let notched = [
+            [0, 0],
+            [1, 0],
+            [1, 2],
+            [3, 2],
+            [3, 0],
+            [4, 0],
+            [4, 3],
+            [0, 3],
+        ]
+        .map(NDCord);
+        assert_eq!(enclosed_points(&notched), 20 - 2);
+
+        let huge = [
+            [-1_000_000_000, -1_000_000_000],
+            [1_000_000_000, -1_000_000_000],
+            [1_000_000_000, 1_000_000_000],
+            [-1_000_000_000, 1_000_000_000],
+        ]
+        .map(NDCord);
+        assert_eq!(shoelace_area_doubled(&huge), 8_000_000_000_000_000_000);
+        assert_eq!(enclosed_points(&huge), 2_000_000_001 * 2_000_000_001);
+        assert_eq!(enclosed_points(&[]), 0);
+        assert_eq!(enclosed_points(&[NDCord([0, 0]), NDCord([3, 0])]), 4);
+    }
+
+    #[test]
+    fn arithmetic_test() {
+        let a = NDCord([-7, 3, 0]);
+        assert_eq!(-a, NDCord([7, -3, 0]));
+        assert_eq!(a % 2, NDCord([-1, 1, 0]));
+        assert_eq!(a.rem_euclid(&NDCord([2, 2, 2])), NDCord([1, 1, 0]));
+        assert_eq!(a.abs(), NDCord([7, 3, 0]));
+        assert_eq!(a.signum(), NDCord([-1, 1, 0]));
+
+        let b = NDCord([2, 5, -1]);
+        assert_eq!(a.component_min(b), NDCord([-7, 3, -1]));
+        assert_eq!(a.component_max(b), NDCord([2, 5, 0]));
+        assert_eq!(
+            NDCord([-9, 4, 10]).component_clamp(NDCord([-1, -1, -1]), NDCord([1, 1, 1])),
+            NDCord([-1, 1, 1])
+        );
+        assert_eq!(a.dot(&b), -14 + 15);
+        let cross = a.cross(&b);
+        assert_eq!(cross, NDCord([-3, -7, -41]));
+        assert_eq!((cross.dot(&a), cross.dot(&b)), (0, 0));
+        assert_eq!(
+            NDCord([1, 0, 0]).cross(&NDCord([0, 1, 0])),
+            NDCord([0, 0, 1])
+        );
+    }
+
+    #[test]
+    fn distance_test() {
+        let (a, b) = (NDCord([1usize, 8]), NDCord([4, 4]));
+        assert_eq!(a.manhattan_distance(&b), 7);
+        assert_eq!(a.chebyshev_distance(&b), 4);
+        assert_eq!(a.euclidean_distance_squared(&b), 25);
+        assert_eq!(a.chebyshev_distance(&a), 0);
+    }
 }
@@ -0,0 +1,450 @@
+use crate::{
+    cord::NDCord,
+    dir::{Dir, Direction},
+};
+use num_traits::{FromPrimitive, ToPrimitive};
+use std::collections::{BTreeSet, HashSet, VecDeque};
+
+pub type Pos = NDCord<isize, 2>;
+type Vec3 = NDCord<isize, 3>;
+
+/// Error when a set of cells can't be folded into a cube.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum CubeNetError {
+    #[error("{0} cells can't be split into 6 square faces")]
+    CellCount(usize),
+    #[error("Cells cover {0} face sized blocks instead of 6")]
+    FaceCount(usize),
+    #[error("Faces aren't all connected through their edges")]
+    Disconnected,
+    #[error("Faces {0} and {1} overlap when folded")]
+    Overlap(usize, usize),
+}
+
+/// Orientation of a face after folding as 3d unit vectors.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Frame {
+    /// Points out of the cube.
+    normal: Vec3,
+    /// Direction of increasing x on the net.
+    right: Vec3,
+    /// Direction of increasing y on the net.
+    down: Vec3,
+}
+
+impl Frame {
+    /// Vector from the center of the face to the center of the edge in `dir`.
+    /// This is also the normal of the face on the other side of that edge.
+    fn edge_normal(&self, dir: Dir) -> Vec3 {
+        match dir {
+            Dir::Right => self.right,
+            Dir::Down => self.down,
+            Dir::Left => self.right * -1,
+            Dir::Up => self.down * -1,
+        }
+    }
+
+    /// Frame of the face attached to the edge in `dir` once it is folded down onto the cube.
+    fn fold(&self, dir: Dir) -> Self {
+        let Frame {
+            normal,
+            right,
+            down,
+        } = *self;
+        match dir {
+            Dir::Right => Frame {
+                normal: right,
+                right: normal * -1,
+                down,
+            },
+            Dir::Left => Frame {
+                normal: right * -1,
+                right: normal,
+                down,
+            },
+            Dir::Down => Frame {
+                normal: down,
+                right,
+                down: normal * -1,
+            },
+            Dir::Up => Frame {
+                normal: down * -1,
+                right,
+                down: normal,
+            },
+        }
+    }
+}
+
+/// Where an edge of a face ends up when the net is folded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CubeEdge {
+    /// Index of the face on the other side of the edge.
+    pub face: usize,
+    /// Edge of that face which is touching.
+    pub edge: Dir,
+    /// Clockwise quarter turns applied to a heading that crosses the edge.
+    pub rotation: u8,
+}
+
+/// Distance along the edge in `dir` going clockwise around a face with sides of `side_len`.
+fn clockwise_offset(local: Pos, dir: Dir, side_len: isize) -> isize {
+    match dir {
+        Dir::Up => local[0],
+        Dir::Right => local[1],
+        Dir::Down => side_len - 1 - local[0],
+        Dir::Left => side_len - 1 - local[1],
+    }
+}
+
+/// Inverse of [`clockwise_offset`].
+fn from_clockwise_offset(offset: isize, dir: Dir, side_len: isize) -> Pos {
+    match dir {
+        Dir::Up => [offset, 0],
+        Dir::Right => [side_len - 1, offset],
+        Dir::Down => [side_len - 1 - offset, side_len - 1],
+        Dir::Left => [0, side_len - 1 - offset],
+    }
+    .into()
+}
+
+/// Unfolded cube made of 6 square faces laid out on a grid.
+///
+/// Faces are numbered in reading order (top to bottom, then left to right) and positions are in the same
+/// cordinate system as the cells used to build the net where Right is increasing x and Down is increasing y.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CubeNet {
+    side_len: isize,
+    /// Top left cell of the net's bounding box.
+    origin: Pos,
+    /// Position of each face in units of faces relative to the origin.
+    faces: Vec<Pos>,
+    /// Edges of each face indexed by [`Dir`].
+    edges: Vec<[CubeEdge; 4]>,
+}
+
+impl CubeNet {
+    /// Build a net from every cell that is part of the cube, finding the side length from the number of cells.
+    /// Fails if the cells aren't one of the 11 nets of a cube.
+    pub fn from_cells(cells: impl IntoIterator<Item = Pos>) -> Result<Self, CubeNetError> {
+        let cells: HashSet<Pos> = cells.into_iter().collect();
+        let side_len = (1..)
+            .take_while(|x| 6 * x * x <= cells.len())
+            .find(|x| 6 * x * x == cells.len())
+            .ok_or(CubeNetError::CellCount(cells.len()))?;
+        let side_len = isize::try_from(side_len).expect("Side length fits in isize");
+        let (origin, _) = NDCord::extents_iter(cells.iter().copied()).expect("Nonempty cells");
+
+        // Which block of the net each cell is part of. Sort by row first so faces are in reading order.
+        let faces: BTreeSet<[isize; 2]> = cells
+            .iter()
+            .map(|&x| {
+                let face = (x - origin) / side_len;
+                [face[1], face[0]]
+            })
+            .collect();
+        if faces.len() != 6 {
+            return Err(CubeNetError::FaceCount(faces.len()));
+        }
+        // With 6 blocks and exactly 6 faces worth of cells every block must be full.
+        let faces: Vec<Pos> = faces.into_iter().map(|[y, x]| [x, y].into()).collect();
+
+        // Fold faces breadth first from the first face.
+        let mut frames: Vec<Option<Frame>> = vec![None; faces.len()];
+        frames[0] = Some(Frame {
+            normal: [0, 0, 1].into(),
+            right: [1, 0, 0].into(),
+            down: [0, 1, 0].into(),
+        });
+        let mut queue = VecDeque::from([0]);
+        while let Some(face) = queue.pop_front() {
+            let frame = frames[face].expect("Only folded faces are queued");
+            for dir in enum_iterator::all::<Dir>() {
+                let neighbor_pos = faces[face] + dir.to_velocity();
+                if let Some(neighbor) = faces.iter().position(|&x| x == neighbor_pos) {
+                    if frames[neighbor].is_none() {
+                        frames[neighbor] = Some(frame.fold(dir));
+                        queue.push_back(neighbor);
+                    }
+                }
+            }
+        }
+        let frames = frames
+            .into_iter()
+            .collect::<Option<Vec<_>>>()
+            .ok_or(CubeNetError::Disconnected)?;
+        for (i, a) in frames.iter().enumerate() {
+            if let Some(j) = frames[i + 1..].iter().position(|b| a.normal == b.normal) {
+                return Err(CubeNetError::Overlap(i, i + 1 + j));
+            }
+        }
+
+        // Every face has a distinct normal so the face across an edge is the one facing the edge's direction.
+        let edges = frames
+            .iter()
+            .map(|frame| {
+                [Dir::Right, Dir::Down, Dir::Left, Dir::Up].map(|dir| {
+                    let face = frames
+                        .iter()
+                        .position(|x| x.normal == frame.edge_normal(dir))
+                        .expect("All 6 normals are present");
+                    let edge = enum_iterator::all::<Dir>()
+                        .find(|&x| frames[face].edge_normal(x) == frame.normal)
+                        .expect("Neighboring faces share an edge");
+                    let rotation = (edge.opposite().to_i8().unwrap() - dir.to_i8().unwrap())
+                        .rem_euclid(4)
+                        .try_into()
+                        .unwrap();
+                    CubeEdge {
+                        face,
+                        edge,
+                        rotation,
+                    }
+                })
+            })
+            .collect();
+
+        Ok(CubeNet {
+            side_len,
+            origin,
+            faces,
+            edges,
+        })
+    }
+
+    /// Length of a side of each face in cells.
+    pub fn side_len(&self) -> isize {
+        self.side_len
+    }
+
+    /// Position of each face in units of faces relative to the top left of the net.
+    pub fn faces(&self) -> &[Pos] {
+        &self.faces
+    }
+
+    /// Top left cell of a face.
+    pub fn face_origin(&self, face: usize) -> Pos {
+        self.origin + self.faces[face] * self.side_len
+    }
+
+    /// Index of the face that contains a cell.
+    pub fn face_of(&self, pos: Pos) -> Option<usize> {
+        let relative = pos - self.origin;
+        if relative.iter().any(|&x| x < 0) {
+            return None;
+        }
+        let face = relative / self.side_len;
+        self.faces.iter().position(|&x| x == face)
+    }
+
+    /// Where the edge of a face in `dir` is attached once folded.
+    pub fn edge(&self, face: usize, dir: Dir) -> CubeEdge {
+        self.edges[face][dir.to_usize().unwrap()]
+    }
+
+    /// Take one step from a cell on the net in `dir` as if walking on the folded cube.
+    /// Returns the next cell and the heading after possibly crossing onto a different face.
+    /// ```
+    /// # use advent_lib::{cube_net::CubeNet, dir::Dir};
+    /// // A cross shaped net.
+    /// //  #
+    /// // ###
+    /// //  #
+    /// //  #
+    /// let cells = [[1, 0], [0, 1], [1, 1], [2, 1], [1, 2], [1, 3]].map(Into::into);
+    /// let net = CubeNet::from_cells(cells).unwrap();
+    /// assert_eq!(net.step([1, 1].into(), Dir::Down), ([1, 2].into(), Dir::Down));
+    /// // Walking up from the top face lands on the bottom of the net still heading up.
+    /// assert_eq!(net.step([1, 0].into(), Dir::Up), ([1, 3].into(), Dir::Up));
+    /// // Walking up from the left face lands on the left side of the top face heading right.
+    /// assert_eq!(net.step([0, 1].into(), Dir::Up), ([1, 0].into(), Dir::Right));
+    /// ```
+    /// # Panics
+    /// If `pos` isn't on the net.
+    pub fn step(&self, pos: Pos, dir: Dir) -> (Pos, Dir) {
+        let face = self.face_of(pos).expect("Position is on the net");
+        let next = pos + dir.to_velocity();
+        if self.face_of(next).is_some() {
+            return (next, dir);
+        }
+        let edge = self.edge(face, dir);
+        let offset = clockwise_offset(pos - self.face_origin(face), dir, self.side_len);
+        // Faces go around a shared edge in opposite directions.
+        let local = from_clockwise_offset(self.side_len - 1 - offset, edge.edge, self.side_len);
+        (
+            self.face_origin(edge.face) + local,
+            Dir::from_u8((dir.to_u8().unwrap() + edge.rotation) % 4).unwrap(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// All 11 nets of a cube where each `#` is a face.
+    const NETS: [&str; 11] = [
+        // 1-4-1
+        "#...\n####\n#...",
+        "#...\n####\n.#..",
+        "#...\n####\n..#.",
+        "#...\n####\n...#",
+        ".#..\n####\n.#..",
+        ".#..\n####\n..#.",
+        // 2-3-1
+        "##...\n.###.\n.#...",
+        "##...\n.###.\n..#..",
+        "##...\n.###.\n...#.",
+        // 2-2-2
+        "##..\n.##.\n..##",
+        // 3-3
+        "###...\n..###.",
+    ];
+
+    /// Cells for a net drawn with one character per face with faces of `side_len`.
+    fn net_cells(net: &str, side_len: isize, offset: Pos) -> Vec<Pos> {
+        net.lines()
+            .enumerate()
+            .flat_map(|(y, line)| {
+                line.chars()
+                    .enumerate()
+                    .filter(|&(_, c)| c == '#')
+                    .map(move |(x, _)| Pos::from([x as isize, y as isize]))
+            })
+            .flat_map(|face| {
+                let top_left = face * side_len + offset;
+                top_left
+                    .interpolate(&(top_left + Pos::from([side_len - 1, side_len - 1])))
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    fn all_cells(net: &CubeNet) -> Vec<Pos> {
+        (0..6)
+            .flat_map(|face| {
+                let top_left = net.face_origin(face);
+                let bottom_right = top_left + Pos::from([net.side_len - 1, net.side_len - 1]);
+                top_left.interpolate(&bottom_right).collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn valid_nets_test() {
+        for net in NETS {
+            for side_len in [1, 2, 4, 50] {
+                let out = CubeNet::from_cells(net_cells(net, side_len, [0, 0].into()))
+                    .unwrap_or_else(|e| panic!("{net:?} with side {side_len}: {e}"));
+                assert_eq!(out.side_len(), side_len);
+                assert_eq!(out.faces().len(), 6);
+            }
+        }
+        // Also every rotation and reflection of each net.
+        for net in NETS {
+            let mut grid: Vec<Vec<char>> = net.lines().map(|x| x.chars().collect()).collect();
+            for _ in 0..4 {
+                grid = (0..grid[0].len())
+                    .map(|x| grid.iter().rev().map(|row| row[x]).collect())
+                    .collect();
+                for reflect in [false, true] {
+                    let drawn = grid
+                        .iter()
+                        .map(|row| {
+                            if reflect {
+                                row.iter().rev().collect::<String>()
+                            } else {
+                                row.iter().collect::<String>()
+                            }
+                        })
+                        .collect::<Vec<_>>()
+                        .join("\n");
+                    assert!(CubeNet::from_cells(net_cells(&drawn, 3, [0, 0].into())).is_ok());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn edges_test() {
+        for net in NETS {
+            let out = CubeNet::from_cells(net_cells(net, 3, [0, 0].into())).unwrap();
+            let mut all_edges = HashSet::new();
+            for face in 0..6 {
+                let neighbors: HashSet<_> = enum_iterator::all::<Dir>()
+                    .map(|dir| out.edge(face, dir).face)
+                    .collect();
+                // Every face touches 4 distinct faces that aren't itself.
+                assert_eq!(neighbors.len(), 4, "{net}");
+                assert!(!neighbors.contains(&face));
+                for dir in enum_iterator::all::<Dir>() {
+                    let edge = out.edge(face, dir);
+                    // Edges are symmetric.
+                    let back = out.edge(edge.face, edge.edge);
+                    assert_eq!((back.face, back.edge), (face, dir), "{net}");
+                    assert_eq!((edge.rotation + back.rotation) % 4, 0);
+                    all_edges.insert((face, dir));
+                    // Faces touching on the net don't change heading.
+                    if out.faces()[edge.face] == out.faces()[face] + dir.to_velocity() {
+                        assert_eq!(edge.rotation, 0);
+                        assert_eq!(edge.edge, dir.opposite());
+                    }
+                }
+            }
+            assert_eq!(all_edges.len(), 24);
+        }
+    }
+
+    #[test]
+    fn step_test() {
+        for net in NETS {
+            let side_len = 4;
+            let out = CubeNet::from_cells(net_cells(net, side_len, [-3, 7].into())).unwrap();
+            for start in all_cells(&out) {
+                for dir in enum_iterator::all::<Dir>() {
+                    // Stepping and turning around returns to the start.
+                    let (next, next_dir) = out.step(start, dir);
+                    assert!(out.face_of(next).is_some());
+                    assert_eq!(out.step(next, next_dir.opposite()), (start, dir.opposite()));
+
+                    // Walking straight goes around the cube once every 4 faces.
+                    let mut cursor = (start, dir);
+                    for _ in 0..4 * side_len {
+                        cursor = out.step(cursor.0, cursor.1);
+                    }
+                    assert_eq!(cursor, (start, dir), "{net} from {start:?} {dir:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn invalid_nets_test() {
+        let origin = Pos::from([0, 0]);
+        assert_eq!(CubeNet::from_cells(vec![]), Err(CubeNetError::CellCount(0)));
+        assert_eq!(
+            CubeNet::from_cells(net_cells("#####", 2, origin)),
+            Err(CubeNetError::CellCount(20))
+        );
+        // Six cells worth of faces but not aligned to a grid of faces.
+        let mut cells = net_cells(NETS[0], 2, origin);
+        cells[0] = [7, 7].into();
+        assert_eq!(CubeNet::from_cells(cells), Err(CubeNetError::FaceCount(7)));
+        assert_eq!(
+            CubeNet::from_cells(net_cells("###\n...\n###", 2, origin)),
+            Err(CubeNetError::Disconnected)
+        );
+        assert_eq!(
+            CubeNet::from_cells(net_cells("###\n###", 2, origin)),
+            Err(CubeNetError::Overlap(3, 4))
+        );
+        assert!(matches!(
+            CubeNet::from_cells(net_cells("####\n##..", 2, origin)),
+            Err(CubeNetError::Overlap(_, _))
+        ));
+        assert!(matches!(
+            CubeNet::from_cells(net_cells("#.#\n###\n#..", 1, origin)),
+            Err(CubeNetError::Overlap(_, _))
+        ));
+    }
+}
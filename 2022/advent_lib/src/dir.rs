@@ -1,8 +1,9 @@
-use crate::cord::NDCord;
-use num_traits::{FromPrimitive, One, ToPrimitive, Zero};
+use crate::{cord::NDCord, topology::Topology};
+use enum_iterator::{all, Sequence};
+use num_traits::{CheckedAdd, CheckedSub, FromPrimitive, One, ToPrimitive, Zero};
 use std::{
     cmp::PartialEq,
-    ops::{MulAssign, Neg},
+    ops::{Add, MulAssign, Neg},
 };
 
 pub type Velocity<T> = NDCord<T, 2>;
@@ -63,6 +64,250 @@ impl Dir {
             _ => panic!("Invalid velocity"),
         }
     }
+
+    /// Move one step from `pos` in this direction. Moving off the edge is resolved by the `topology`.
+    pub fn step<T, Top>(self, pos: Velocity<T>, topology: &Top) -> Option<Velocity<T>>
+    where
+        T: Zero + One + Neg<Output = T> + Add<Output = T>,
+        Top: Topology<T, 2>,
+    {
+        topology.step(pos, self.to_velocity())
+    }
+}
+
+/// Behavior shared by the direction types so movement code doesn't depend on how many directions there are.
+pub trait Direction<const DIM: usize>: Copy + Eq + Sequence {
+    fn opposite(self) -> Self;
+
+    /// Turn clockwise or counter-clockwise by the smallest step between directions.
+    fn rotate(self, rotation: &Rotation) -> Self;
+
+    /// Velocity one step in this direction with Right/East increasing x and Down/South increasing y.
+    fn to_velocity<T>(self) -> NDCord<T, DIM>
+    where
+        T: Zero + One + Neg<Output = T>;
+
+    /// Direction with exactly the given velocity.
+    fn try_from_velocity<T>(velocity: &NDCord<T, DIM>) -> Option<Self>
+    where
+        T: Zero + One + Neg<Output = T> + PartialEq,
+    {
+        all::<Self>().find(|dir| dir.to_velocity::<T>() == *velocity)
+    }
+
+    /// Parse from an arrow (`^v<>`), a letter for up/down/left/right (`UDLR`) or a compass letter (`NSEW`).
+    fn from_char(c: char) -> Option<Self>;
+
+    /// The radius 1 moore neighbors of `pos` that are at least partly in this direction.
+    /// ```
+    /// # use advent_lib::{cord::NDCord, dir::{Compass, Dir, Direction}};
+    /// let ahead: Vec<_> = Dir::Up.in_front(NDCord([0, 0])).collect();
+    /// assert_eq!(ahead, vec![NDCord([-1, -1]), NDCord([0, -1]), NDCord([1, -1])]);
+    /// let ahead: Vec<_> = Compass::NorthEast.in_front(NDCord([0, 0])).collect();
+    /// assert_eq!(ahead, vec![NDCord([0, -1]), NDCord([1, -1]), NDCord([1, 0])]);
+    /// ```
+    fn in_front<T>(self, pos: NDCord<T, DIM>) -> impl Iterator<Item = NDCord<T, DIM>>
+    where
+        T: CheckedAdd + CheckedSub + One + Clone,
+    {
+        let heading = self.to_velocity::<i8>();
+        NDCord([0i8; DIM])
+            .moore_neighborhood(1)
+            .filter(move |offset| (0..DIM).map(|i| offset[i] * heading[i]).sum::<i8>() > 0)
+            .filter_map(move |offset| {
+                // Step each axis by one instead of adding the offset so unsigned cordinates work.
+                // Cells past the edge of `T`, like below 0 for unsigned cordinates, are skipped.
+                let mut cell = pos.clone();
+                for i in 0..DIM {
+                    cell[i] = match offset[i] {
+                        1 => pos[i].checked_add(&T::one())?,
+                        -1 => pos[i].checked_sub(&T::one())?,
+                        _ => continue,
+                    };
+                }
+                Some(cell)
+            })
+    }
+}
+
+/// Letter shared by each direction in the `^v<>`, `UDLR` and `NSEW` notations.
+fn normalize_dir_char(c: char) -> char {
+    match c {
+        '^' | 'U' | 'N' => 'N',
+        'v' | 'D' | 'S' => 'S',
+        '<' | 'L' | 'W' => 'W',
+        '>' | 'R' | 'E' => 'E',
+        c => c,
+    }
+}
+
+impl Direction<2> for Dir {
+    fn opposite(self) -> Self {
+        self.rotate(&Rotation::Right).rotate(&Rotation::Right)
+    }
+
+    fn rotate(self, rotation: &Rotation) -> Self {
+        Dir::rotate(self, rotation)
+    }
+
+    fn to_velocity<T>(self) -> Velocity<T>
+    where
+        T: Zero + One + Neg<Output = T>,
+    {
+        Dir::to_velocity(self)
+    }
+
+    fn from_char(c: char) -> Option<Self> {
+        match normalize_dir_char(c) {
+            'N' => Some(Dir::Up),
+            'S' => Some(Dir::Down),
+            'W' => Some(Dir::Left),
+            'E' => Some(Dir::Right),
+            _ => None,
+        }
+    }
+}
+
+// Eight way direction where the discriminant represents the number of clockwise 45 degree turns from the east.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Hash,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    num_derive::FromPrimitive,
+    num_derive::ToPrimitive,
+    enum_iterator::Sequence,
+)]
+pub enum Compass {
+    East = 0,
+    SouthEast = 1,
+    South = 2,
+    SouthWest = 3,
+    West = 4,
+    NorthWest = 5,
+    North = 6,
+    NorthEast = 7,
+}
+
+impl Direction<2> for Compass {
+    fn opposite(self) -> Self {
+        Self::from_i8((self.to_i8().unwrap() + 4).rem_euclid(8)).unwrap()
+    }
+
+    /// Turn 45 degrees.
+    fn rotate(self, rotation: &Rotation) -> Self {
+        match rotation {
+            Rotation::Right => Self::from_i8((self.to_i8().unwrap() + 1).rem_euclid(8)).unwrap(),
+            Rotation::Left => Self::from_i8((self.to_i8().unwrap() - 1).rem_euclid(8)).unwrap(),
+        }
+    }
+
+    fn to_velocity<T>(self) -> Velocity<T>
+    where
+        T: Zero + One + Neg<Output = T>,
+    {
+        match self {
+            Compass::East => [T::one(), T::zero()],
+            Compass::SouthEast => [T::one(), T::one()],
+            Compass::South => [T::zero(), T::one()],
+            Compass::SouthWest => [-T::one(), T::one()],
+            Compass::West => [-T::one(), T::zero()],
+            Compass::NorthWest => [-T::one(), -T::one()],
+            Compass::North => [T::zero(), -T::one()],
+            Compass::NorthEast => [T::one(), -T::one()],
+        }
+        .into()
+    }
+
+    fn from_char(c: char) -> Option<Self> {
+        Dir::from_char(c).map(Compass::from)
+    }
+}
+
+impl From<Dir> for Compass {
+    fn from(dir: Dir) -> Self {
+        match dir {
+            Dir::Right => Compass::East,
+            Dir::Down => Compass::South,
+            Dir::Left => Compass::West,
+            Dir::Up => Compass::North,
+        }
+    }
+}
+
+// Direction along one of three axes where the discriminant modulo 3 is the axis and the first three are positive.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Hash,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    num_derive::FromPrimitive,
+    num_derive::ToPrimitive,
+    enum_iterator::Sequence,
+)]
+pub enum Dir3 {
+    Right = 0,
+    Down = 1,
+    /// Increasing z.
+    Forward = 2,
+    Left = 3,
+    Up = 4,
+    Backward = 5,
+}
+
+impl Direction<3> for Dir3 {
+    fn opposite(self) -> Self {
+        Self::from_i8((self.to_i8().unwrap() + 3).rem_euclid(6)).unwrap()
+    }
+
+    /// Turn about the z axis like [`Dir::rotate`]. Forward and Backward are unchanged.
+    fn rotate(self, rotation: &Rotation) -> Self {
+        match self {
+            Dir3::Right => Dir::Right.rotate(rotation).into(),
+            Dir3::Down => Dir::Down.rotate(rotation).into(),
+            Dir3::Left => Dir::Left.rotate(rotation).into(),
+            Dir3::Up => Dir::Up.rotate(rotation).into(),
+            Dir3::Forward | Dir3::Backward => self,
+        }
+    }
+
+    fn to_velocity<T>(self) -> NDCord<T, 3>
+    where
+        T: Zero + One + Neg<Output = T>,
+    {
+        let index = self.to_usize().unwrap();
+        let mut out = NDCord([T::zero(), T::zero(), T::zero()]);
+        out[index % 3] = if index < 3 { T::one() } else { -T::one() };
+        out
+    }
+
+    /// Also accepts `F` and `B` for Forward and Backward.
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            'F' => Some(Dir3::Forward),
+            'B' => Some(Dir3::Backward),
+            c => Dir::from_char(c).map(Dir3::from),
+        }
+    }
+}
+
+impl From<Dir> for Dir3 {
+    fn from(dir: Dir) -> Self {
+        match dir {
+            Dir::Right => Dir3::Right,
+            Dir::Down => Dir3::Down,
+            Dir::Left => Dir3::Left,
+            Dir::Up => Dir3::Up,
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
@@ -82,3 +327,68 @@ pub fn rotate<T: MulAssign + One + Neg<Output = T>>(dir: &mut Velocity<T>, rotat
         Rotation::Left => dir[1] *= -T::one(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check_direction<D: Direction<DIM> + std::fmt::Debug, const DIM: usize>() {
+        for dir in all::<D>() {
+            let velocity = dir.to_velocity::<i32>();
+            assert_eq!(D::try_from_velocity(&velocity), Some(dir));
+            assert_eq!(dir.opposite().to_velocity(), velocity * -1);
+            assert_eq!(dir.opposite().opposite(), dir);
+            assert_eq!(dir.rotate(&Rotation::Right).rotate(&Rotation::Left), dir);
+        }
+    }
+
+    #[test]
+    fn direction_test() {
+        check_direction::<Dir, 2>();
+        check_direction::<Compass, 2>();
+        check_direction::<Dir3, 3>();
+        assert_eq!(Compass::try_from_velocity(&NDCord([2, 0])), None);
+        assert_eq!(Compass::North.rotate(&Rotation::Right), Compass::NorthEast);
+        assert_eq!(Dir3::Up.rotate(&Rotation::Left), Dir3::Left);
+        assert_eq!(Dir3::Forward.rotate(&Rotation::Left), Dir3::Forward);
+        assert_eq!(Dir3::Backward.to_velocity::<i32>(), NDCord([0, 0, -1]));
+    }
+
+    #[test]
+    fn from_char_test() {
+        for (chars, dir) in [
+            ("^UN", Dir::Up),
+            ("vDS", Dir::Down),
+            ("<LW", Dir::Left),
+            (">RE", Dir::Right),
+        ] {
+            for c in chars.chars() {
+                assert_eq!(Dir::from_char(c), Some(dir));
+                assert_eq!(Compass::from_char(c), Some(dir.into()));
+                assert_eq!(Dir3::from_char(c), Some(dir.into()));
+            }
+        }
+        assert_eq!(Dir::from_char('F'), None);
+        assert_eq!(Dir3::from_char('F'), Some(Dir3::Forward));
+    }
+
+    #[test]
+    fn in_front_test() {
+        for dir in all::<Compass>() {
+            let ahead: Vec<_> = dir.in_front(NDCord([5, 5])).collect();
+            assert_eq!(ahead.len(), 3);
+            assert!(ahead.contains(&(NDCord([5, 5]) + dir.to_velocity())));
+        }
+        assert_eq!(
+            Dir::Right.in_front(NDCord([0usize, 1])).collect::<Vec<_>>(),
+            vec![NDCord([1, 0]), NDCord([1, 1]), NDCord([1, 2])]
+        );
+        // Cells below 0 don't exist for unsigned cordinates.
+        assert_eq!(Dir::Left.in_front(NDCord([0usize, 1])).count(), 0);
+        assert_eq!(
+            Dir::Up.in_front(NDCord([0usize, 1])).collect::<Vec<_>>(),
+            vec![NDCord([0, 0]), NDCord([1, 0])]
+        );
+        assert_eq!(Dir3::Forward.in_front(NDCord([0, 0, 0])).count(), 9);
+    }
+}
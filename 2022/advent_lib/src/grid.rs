@@ -0,0 +1,260 @@
+use crate::cord::NDCord;
+use num_traits::{cast, NumCast};
+use std::{
+    collections::HashSet,
+    hash::Hash,
+    ops::{Add, Sub},
+};
+
+/// How cells of a boolean occupancy map are packed into characters when rendered.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RenderMode {
+    /// One character per cell.
+    Char { occupied: char, empty: char },
+    /// Two rows per character using `▀`, `▄`, `█` and ` `.
+    HalfBlock,
+    /// Two columns and four rows per character using braille patterns (`U+2800..=U+28FF`).
+    Braille,
+}
+
+impl Default for RenderMode {
+    fn default() -> Self {
+        RenderMode::Char {
+            occupied: '#',
+            empty: '.',
+        }
+    }
+}
+
+impl RenderMode {
+    /// Number of cells `(columns, rows)` that are drawn by a single character.
+    pub fn cells_per_char(&self) -> (usize, usize) {
+        match self {
+            RenderMode::Char { .. } => (1, 1),
+            RenderMode::HalfBlock => (1, 2),
+            RenderMode::Braille => (2, 4),
+        }
+    }
+
+    /// Character for a block of cells where `occupied(column, row)` is relative to the top left of the block.
+    fn block_char(&self, occupied: impl Fn(usize, usize) -> bool) -> char {
+        match *self {
+            RenderMode::Char {
+                occupied: on,
+                empty: off,
+            } => {
+                if occupied(0, 0) {
+                    on
+                } else {
+                    off
+                }
+            }
+            RenderMode::HalfBlock => match (occupied(0, 0), occupied(0, 1)) {
+                (false, false) => ' ',
+                (true, false) => '▀',
+                (false, true) => '▄',
+                (true, true) => '█',
+            },
+            RenderMode::Braille => {
+                // Bit for each dot indexed by `[row][column]`.
+                const DOT_BITS: [[u32; 2]; 4] = [[0, 3], [1, 4], [2, 5], [6, 7]];
+                let mut pattern = 0;
+                for (row, bits) in DOT_BITS.iter().enumerate() {
+                    for (column, bit) in bits.iter().enumerate() {
+                        if occupied(column, row) {
+                            pattern |= 1 << bit;
+                        }
+                    }
+                }
+                char::from_u32(0x2800 + pattern).expect("Braille patterns are valid chars")
+            }
+        }
+    }
+}
+
+/// Render the cells between the `(min, max)` extents (inclusive) where `occupied` decides if a cell is filled.
+///
+/// The x axis (`[0]`) increases to the right and the y axis (`[1]`) increases downwards, like the puzzle inputs.
+/// Every line, including the last, ends with `\n`. Blocks that extend past the extents are padded with empty cells.
+/// ```
+/// # use advent_lib::{cord::NDCord, grid::{render, RenderMode}};
+/// let extents = (NDCord([0, 0]), NDCord([2, 1]));
+/// let occupied = |x: &NDCord<i32, 2>| x[0] == x[1];
+/// assert_eq!(render(&extents, occupied, RenderMode::default()), "#..\n.#.\n");
+/// assert_eq!(render(&extents, occupied, RenderMode::HalfBlock), "▀▄ \n");
+/// ```
+pub fn render<T>(
+    extents: &(NDCord<T, 2>, NDCord<T, 2>),
+    occupied: impl Fn(&NDCord<T, 2>) -> bool,
+    mode: RenderMode,
+) -> String
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + NumCast,
+{
+    let (min, max) = extents;
+    let width = (max[0] - min[0])
+        .to_usize()
+        .expect("Extents should be ordered as (min, max)")
+        + 1;
+    let height = (max[1] - min[1])
+        .to_usize()
+        .expect("Extents should be ordered as (min, max)")
+        + 1;
+    let (block_width, block_height) = mode.cells_per_char();
+
+    let mut out = String::new();
+    for block_y in (0..height).step_by(block_height) {
+        for block_x in (0..width).step_by(block_width) {
+            out.push(mode.block_char(|column, row| {
+                let (x, y) = (block_x + column, block_y + row);
+                x < width
+                    && y < height
+                    && occupied(
+                        &(*min
+                            + NDCord([
+                                cast(x).expect("Offset fits in cord's datatype"),
+                                cast(y).expect("Offset fits in cord's datatype"),
+                            ])),
+                    )
+            }));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Render a collection of occupied cells using the smallest extents that contain all of them.
+/// An empty collection renders as an empty [`String`].
+pub fn render_cells<T>(cells: impl IntoIterator<Item = NDCord<T, 2>>, mode: RenderMode) -> String
+where
+    T: Copy + Ord + Hash + Add<Output = T> + Sub<Output = T> + NumCast,
+{
+    let cells: HashSet<_> = cells.into_iter().collect();
+    match NDCord::extents_iter(cells.iter().copied()) {
+        Some(extents) => render(&extents, |x| cells.contains(x), mode),
+        None => String::new(),
+    }
+}
+
+/// Every cell of a rectangular grid indexed as `grid[y][x]` in parallel, paired with its cord `[x, y]`.
+///
+/// Cells are split across threads by their position in row-major order, which is also the order results are collected in.
+/// ```
+/// # use advent_lib::{cord::NDCord, grid::par_cells};
+/// use rayon::prelude::*;
+/// let grid = vec![vec![3, 1, 4], vec![1, 5, 9]];
+/// let (cord, _) = par_cells(&grid).max_by_key(|&(_, x)| x).unwrap();
+/// assert_eq!(cord, NDCord([2, 1]));
+/// ```
+#[cfg(feature = "rayon")]
+pub fn par_cells<V: Sync>(
+    grid: &[Vec<V>],
+) -> impl rayon::iter::IndexedParallelIterator<Item = (NDCord<usize, 2>, &V)> {
+    use crate::iters::NDCartesianProduct;
+    use rayon::prelude::*;
+
+    let width = grid.first().map_or(0, Vec::len);
+    assert!(
+        grid.iter().all(|row| row.len() == width),
+        "Grid should be rectangular"
+    );
+    NDCartesianProduct::new([0..grid.len(), 0..width])
+        .into_par_iter()
+        .map(move |[y, x]| (NDCord([x, y]), &grid[y][x]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // ##...
+    // #.#..
+    // .....
+    // ....#
+    // #####
+    fn example() -> Vec<NDCord<isize, 2>> {
+        #[rustfmt::skip]
+        let cells = vec![
+            [0, 0], [1, 0],
+            [0, 1], [2, 1],
+            [4, 3],
+            [0, 4], [1, 4], [2, 4], [3, 4], [4, 4],
+        ];
+        cells.into_iter().map(NDCord).collect()
+    }
+
+    #[test]
+    fn render_char_test() {
+        assert_eq!(
+            render_cells(example(), RenderMode::default()),
+            "##...\n#.#..\n.....\n....#\n#####\n"
+        );
+        assert_eq!(
+            render_cells(
+                example(),
+                RenderMode::Char {
+                    occupied: 'o',
+                    empty: ' '
+                }
+            ),
+            "oo   \no o  \n     \n    o\nooooo\n"
+        );
+        // Offset extents render relative to the smallest cell.
+        let shifted = example().into_iter().map(|x| x + NDCord([-7, 12]));
+        assert_eq!(
+            render_cells(shifted, RenderMode::default()),
+            "##...\n#.#..\n.....\n....#\n#####\n"
+        );
+    }
+
+    #[test]
+    fn render_half_block_test() {
+        assert_eq!(
+            render_cells(example(), RenderMode::HalfBlock),
+            "█▀▄  \n    ▄\n▀▀▀▀▀\n"
+        );
+    }
+
+    #[test]
+    fn render_braille_test() {
+        // Top left block has dots 1, 2 and 4 set. Bottom blocks only have their top row.
+        assert_eq!(
+            render_cells(example(), RenderMode::Braille),
+            "\u{280B}\u{2802}\u{2840}\n\u{2809}\u{2809}\u{2801}\n"
+        );
+        // A full block fills every dot.
+        let full = NDCord([0, 0]).interpolate(&NDCord([1, 3]));
+        assert_eq!(render_cells(full, RenderMode::Braille), "\u{28FF}\n");
+    }
+
+    #[test]
+    fn render_empty_test() {
+        assert_eq!(
+            render_cells(Vec::<NDCord<isize, 2>>::new(), RenderMode::Braille),
+            ""
+        );
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn par_cells_test() {
+        use rayon::prelude::*;
+
+        let grid: Vec<Vec<_>> = (0..4)
+            .map(|y| (0..5).map(|x| x * 10 + y).collect())
+            .collect();
+        let cells: Vec<_> = par_cells(&grid).map(|(cord, &x)| (cord, x)).collect();
+        assert_eq!(cells.len(), 20);
+        for (cord, x) in cells {
+            assert_eq!(x, cord[0] * 10 + cord[1]);
+        }
+        assert_eq!(
+            par_cells(&grid).map(|(cord, _)| cord).collect::<Vec<_>>(),
+            NDCord([0, 0])
+                .interpolate(&NDCord([3, 4]))
+                .map(|NDCord([y, x])| NDCord([x, y]))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(par_cells::<u8>(&[]).count(), 0);
+    }
+}
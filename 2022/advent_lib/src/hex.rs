@@ -0,0 +1,454 @@
+use crate::{cord::NDCord, dir::Rotation};
+use enum_iterator::all;
+use num_traits::{cast, FromPrimitive, NumCast, Signed, ToPrimitive};
+use std::{
+    iter,
+    ops::{Add, Mul, Neg, Sub},
+};
+use thiserror::Error;
+
+/// Hexagon in cube coordinates `(q, r, s)` where `q + r + s == 0`.
+///
+/// Axial coordinates are the `(q, r)` pair with `s` left implicit.
+/// Directions are named for the [`Orientation::PointyTop`] layout where `q` increases to the east and `r` increases to the south east.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Hex<T>(NDCord<T, 3>);
+
+impl<T: Signed + Copy> Hex<T> {
+    /// Hex from axial coordinates.
+    pub fn new(q: T, r: T) -> Self {
+        Hex(NDCord([q, r, -q - r]))
+    }
+
+    pub fn q(&self) -> T {
+        self.0[0]
+    }
+
+    pub fn r(&self) -> T {
+        self.0[1]
+    }
+
+    pub fn s(&self) -> T {
+        self.0[2]
+    }
+
+    /// The axial `(q, r)` pair.
+    pub fn axial(&self) -> NDCord<T, 2> {
+        NDCord([self.q(), self.r()])
+    }
+
+    /// Number of steps between two hexes.
+    /// ```
+    /// # use advent_lib::hex::Hex;
+    /// assert_eq!(Hex::new(0, 0).distance(&Hex::new(2, -3)), 3);
+    /// ```
+    pub fn distance(&self, other: &Self) -> T {
+        let diff = *self - *other;
+        (diff.q().abs() + diff.r().abs() + diff.s().abs()) / (T::one() + T::one())
+    }
+
+    pub fn neighbor(self, dir: HexDir) -> Self {
+        self + dir.to_velocity()
+    }
+
+    /// The six adjacent hexes in [`HexDir`] order.
+    pub fn neighbors(self) -> impl Iterator<Item = Self> {
+        all::<HexDir>().map(move |dir| self.neighbor(dir))
+    }
+
+    /// Hexes exactly `radius` steps away, clockwise starting from the north west corner.
+    /// A radius of `0` is only `self`.
+    pub fn ring(self, radius: usize) -> impl Iterator<Item = Self>
+    where
+        T: NumCast,
+    {
+        let radius_t: T = cast(radius).expect("Can't convert radius to cord's datatype.");
+        let start = self + HexDir::NorthWest.to_velocity() * radius_t;
+        let edges = all::<HexDir>().flat_map(move |dir| iter::repeat_n(dir, radius));
+        (radius == 0)
+            .then_some(self)
+            .into_iter()
+            .chain(edges.scan(start, |cur, dir| {
+                let out = *cur;
+                *cur = cur.neighbor(dir);
+                Some(out)
+            }))
+    }
+
+    /// Every hex at most `radius` steps away, ordered by [`Hex::ring`] from the center outwards.
+    pub fn spiral(self, radius: usize) -> impl Iterator<Item = Self>
+    where
+        T: NumCast,
+    {
+        (0..=radius).flat_map(move |radius| self.ring(radius))
+    }
+
+    /// Rotate 60 degrees about the origin.
+    pub fn rotate(self, rotation: &Rotation) -> Self {
+        let [q, r, s] = self.0 .0;
+        match rotation {
+            Rotation::Right => Hex(NDCord([-r, -s, -q])),
+            Rotation::Left => Hex(NDCord([-s, -q, -r])),
+        }
+    }
+
+    /// Rotate 60 degrees about `center`.
+    pub fn rotate_around(self, center: Self, rotation: &Rotation) -> Self {
+        (self - center).rotate(rotation) + center
+    }
+}
+
+impl<T: Add<Output = T>> Add for Hex<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Hex(self.0 + rhs.0)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Hex<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Hex(self.0 - rhs.0)
+    }
+}
+
+impl<T: Mul<Output = T> + Clone> Mul<T> for Hex<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Hex(self.0 * rhs)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Hex<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Hex(NDCord(self.0 .0.map(T::neg)))
+    }
+}
+
+impl<T> From<Hex<T>> for NDCord<T, 3> {
+    fn from(hex: Hex<T>) -> Self {
+        hex.0
+    }
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("cube coordinates must sum to zero")]
+pub struct NotOnPlane;
+
+impl<T: Signed + Copy> TryFrom<NDCord<T, 3>> for Hex<T> {
+    type Error = NotOnPlane;
+
+    fn try_from(cord: NDCord<T, 3>) -> Result<Self, Self::Error> {
+        if (cord[0] + cord[1] + cord[2]).is_zero() {
+            Ok(Hex(cord))
+        } else {
+            Err(NotOnPlane)
+        }
+    }
+}
+
+impl<T: Signed + Copy> From<NDCord<T, 2>> for Hex<T> {
+    /// From axial coordinates.
+    fn from(axial: NDCord<T, 2>) -> Self {
+        Hex::new(axial[0], axial[1])
+    }
+}
+
+// Direction where the discriminant represents the number of clockwise turns from the east.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Hash,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    num_derive::FromPrimitive,
+    num_derive::ToPrimitive,
+    enum_iterator::Sequence,
+)]
+pub enum HexDir {
+    East = 0,
+    SouthEast = 1,
+    SouthWest = 2,
+    West = 3,
+    NorthWest = 4,
+    NorthEast = 5,
+}
+
+impl HexDir {
+    /// Turn 60 degrees.
+    pub fn rotate(self, rotation: &Rotation) -> Self {
+        match rotation {
+            Rotation::Right => Self::from_i8((self.to_i8().unwrap() + 1).rem_euclid(6)).unwrap(),
+            Rotation::Left => Self::from_i8((self.to_i8().unwrap() - 1).rem_euclid(6)).unwrap(),
+        }
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::from_i8((self.to_i8().unwrap() + 3).rem_euclid(6)).unwrap()
+    }
+
+    pub fn to_velocity<T: Signed + Copy>(self) -> Hex<T> {
+        let (zero, one) = (T::zero(), T::one());
+        match self {
+            HexDir::East => Hex::new(one, zero),
+            HexDir::SouthEast => Hex::new(zero, one),
+            HexDir::SouthWest => Hex::new(-one, one),
+            HexDir::West => Hex::new(-one, zero),
+            HexDir::NorthWest => Hex::new(zero, -one),
+            HexDir::NorthEast => Hex::new(one, -one),
+        }
+    }
+
+    /// Assumes that velocity is one step in some direction.
+    pub fn from_velocity<T: Signed + Copy>(velocity: Hex<T>) -> Self {
+        all::<HexDir>()
+            .find(|dir| dir.to_velocity() == velocity)
+            .expect("Invalid velocity")
+    }
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum HexParseError {
+    #[error("cell at line {line} column {column} is between two hexes")]
+    Misaligned { line: usize, column: usize },
+}
+
+/// How hexes are laid out in text.
+///
+/// Both layouts put a hex on every other character of a line and shift every other line by one character.
+/// In [`Orientation::PointyTop`] hexes on the same line are neighbors.
+/// In [`Orientation::FlatTop`] hexes in the same column are neighbors.
+/// ```text
+///  a b c
+/// d e f
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Orientation {
+    PointyTop,
+    FlatTop,
+}
+
+impl Orientation {
+    /// `(column, line)` of the hex in text where both are even or both are odd.
+    fn hex_to_doubled<T: Signed + Copy + ToPrimitive>(self, hex: Hex<T>) -> (isize, isize) {
+        let q = hex.q().to_isize().expect("Hex fits in isize");
+        let r = hex.r().to_isize().expect("Hex fits in isize");
+        match self {
+            Orientation::PointyTop => (2 * q + r, r),
+            Orientation::FlatTop => (q, 2 * r + q),
+        }
+    }
+
+    fn doubled_to_hex<T: Signed + Copy + NumCast>(self, (column, line): (isize, isize)) -> Hex<T> {
+        let (q, r) = match self {
+            Orientation::PointyTop => ((column - line) / 2, line),
+            Orientation::FlatTop => (column, (line - column) / 2),
+        };
+        Hex::new(
+            cast(q).expect("Offset fits in cord's datatype"),
+            cast(r).expect("Offset fits in cord's datatype"),
+        )
+    }
+
+    /// Parse every non-whitespace character as a hex. The first character is the hex at the origin's row.
+    ///
+    /// Whether even or odd columns hold hexes on the first line is taken from the first character so the text may be indented either way.
+    /// ```
+    /// # use advent_lib::hex::{Hex, Orientation};
+    /// let hexes = Orientation::PointyTop.parse::<i32>(" a b\nc d").unwrap();
+    /// assert_eq!(hexes[0], (Hex::new(0, 0), 'a'));
+    /// assert_eq!(hexes[3], (Hex::new(0, 1), 'd'));
+    /// ```
+    pub fn parse<T: Signed + Copy + NumCast>(
+        self,
+        input: &str,
+    ) -> Result<Vec<(Hex<T>, char)>, HexParseError> {
+        let mut parity = None;
+        let mut out = Vec::new();
+        for (line, text) in input.lines().enumerate() {
+            for (column, c) in text.chars().enumerate() {
+                if c.is_whitespace() {
+                    continue;
+                }
+                let (column_i, line_i) = (column as isize, line as isize);
+                let parity = *parity.get_or_insert((column_i + line_i).rem_euclid(2));
+                if (column_i + line_i).rem_euclid(2) != parity {
+                    return Err(HexParseError::Misaligned {
+                        line: line + 1,
+                        column: column + 1,
+                    });
+                }
+                out.push((self.doubled_to_hex((column_i - parity, line_i)), c));
+            }
+        }
+        Ok(out)
+    }
+
+    /// Inverse of [`Orientation::parse`] with the text shifted so the smallest line and column are at the top left.
+    ///
+    /// Every line ends with `\n` and has no trailing whitespace. If a hex appears more than once the last character is used.
+    pub fn render<T: Signed + Copy + ToPrimitive>(
+        self,
+        cells: impl IntoIterator<Item = (Hex<T>, char)>,
+    ) -> String {
+        let cells: Vec<_> = cells
+            .into_iter()
+            .map(|(hex, c)| (self.hex_to_doubled(hex), c))
+            .collect();
+        let (Some(min_column), Some(min_line)) = (
+            cells.iter().map(|x| x.0 .0).min(),
+            cells.iter().map(|x| x.0 .1).min(),
+        ) else {
+            return String::new();
+        };
+        let mut lines: Vec<Vec<char>> = Vec::new();
+        for ((column, line), c) in cells {
+            let (column, line) = ((column - min_column) as usize, (line - min_line) as usize);
+            if lines.len() <= line {
+                lines.resize(line + 1, Vec::new());
+            }
+            if lines[line].len() <= column {
+                lines[line].resize(column + 1, ' ');
+            }
+            lines[line][column] = c;
+        }
+        lines
+            .into_iter()
+            .flat_map(|line| line.into_iter().chain(iter::once('\n')))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn conversion_test() {
+        let hex = Hex::new(2, -5);
+        assert_eq!(NDCord::from(hex), NDCord([2, -5, 3]));
+        assert_eq!(Hex::try_from(NDCord([2, -5, 3])), Ok(hex));
+        assert_eq!(Hex::try_from(NDCord([2, -5, 2])), Err(NotOnPlane));
+        assert_eq!(Hex::from(hex.axial()), hex);
+    }
+
+    #[test]
+    fn dir_test() {
+        for dir in all::<HexDir>() {
+            assert_eq!(HexDir::from_velocity(dir.to_velocity::<i32>()), dir);
+            assert_eq!(-dir.to_velocity::<i32>(), dir.opposite().to_velocity());
+            assert_eq!(
+                dir.to_velocity::<i32>().rotate(&Rotation::Right),
+                dir.rotate(&Rotation::Right).to_velocity()
+            );
+            assert_eq!(dir.rotate(&Rotation::Left).rotate(&Rotation::Right), dir);
+        }
+        assert_eq!(HexDir::NorthEast.rotate(&Rotation::Right), HexDir::East);
+    }
+
+    #[test]
+    fn distance_test() {
+        let center = Hex::new(3, -1);
+        for radius in 0..5 {
+            assert!(center
+                .ring(radius)
+                .all(|x| center.distance(&x) == radius as i32));
+        }
+        assert_eq!(Hex::new(-2, 0).distance(&Hex::new(1, 1)), 4);
+    }
+
+    #[test]
+    fn ring_test() {
+        let center = Hex::new(0, 0);
+        assert_eq!(center.ring(0).collect::<Vec<_>>(), vec![center]);
+        assert_eq!(
+            center.ring(1).collect::<Vec<_>>(),
+            vec![
+                Hex::new(0, -1),
+                Hex::new(1, -1),
+                Hex::new(1, 0),
+                Hex::new(0, 1),
+                Hex::new(-1, 1),
+                Hex::new(-1, 0),
+            ]
+        );
+        assert_eq!(
+            center.ring(1).collect::<HashSet<_>>(),
+            center.neighbors().collect()
+        );
+        assert_eq!(center.ring(3).count(), 18);
+        let spiral = Hex::new(4, 4).spiral(3).collect::<Vec<_>>();
+        assert_eq!(spiral.len(), 37);
+        assert_eq!(spiral.iter().collect::<HashSet<_>>().len(), 37);
+    }
+
+    #[test]
+    fn rotate_test() {
+        let hex = Hex::new(3, -1);
+        let center = Hex::new(1, 1);
+        let mut rotated = hex;
+        for _ in 0..6 {
+            rotated = rotated.rotate_around(center, &Rotation::Left);
+            assert_eq!(rotated.distance(&center), hex.distance(&center));
+        }
+        assert_eq!(rotated, hex);
+        assert_eq!(hex.rotate(&Rotation::Right).rotate(&Rotation::Left), hex);
+    }
+
+    #[test]
+    fn parse_test() {
+        let input = "a b c\n d e\nf g h\n";
+        let pointy = Orientation::PointyTop.parse::<i32>(input).unwrap();
+        let flat = Orientation::FlatTop.parse::<i32>(input).unwrap();
+        let find = |hexes: &[(Hex<i32>, char)], c| hexes.iter().find(|x| x.1 == c).unwrap().0;
+
+        // Hexes on the same line are neighbors when pointy.
+        assert_eq!(
+            find(&pointy, 'b'),
+            find(&pointy, 'a').neighbor(HexDir::East)
+        );
+        assert_eq!(
+            find(&pointy, 'd'),
+            find(&pointy, 'a').neighbor(HexDir::SouthEast)
+        );
+        assert_eq!(
+            find(&pointy, 'f'),
+            find(&pointy, 'd').neighbor(HexDir::SouthWest)
+        );
+        // Hexes in the same column are neighbors when flat.
+        assert_eq!(find(&flat, 'a').distance(&find(&flat, 'f')), 1);
+        assert_eq!(find(&flat, 'a').distance(&find(&flat, 'b')), 2);
+        assert_eq!(find(&flat, 'a').distance(&find(&flat, 'd')), 1);
+
+        for orientation in [Orientation::PointyTop, Orientation::FlatTop] {
+            let hexes = orientation.parse::<i32>(input).unwrap();
+            assert_eq!(orientation.render(hexes), input);
+        }
+        // Indentation of the first line is decided by the first cell.
+        assert_eq!(
+            Orientation::PointyTop.render(Orientation::PointyTop.parse::<i32>(" a\nb c").unwrap()),
+            " a\nb c\n"
+        );
+        assert_eq!(
+            Orientation::FlatTop.render(Vec::<(Hex<i32>, char)>::new()),
+            ""
+        );
+    }
+
+    #[test]
+    fn parse_misaligned_test() {
+        assert_eq!(
+            Orientation::PointyTop.parse::<i32>("a b\nc d"),
+            Err(HexParseError::Misaligned { line: 2, column: 1 })
+        );
+    }
+}
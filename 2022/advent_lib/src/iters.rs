@@ -1,71 +1,300 @@
-use std::array;
-
-/// Determines next value of products in lexicographic order.
-fn next_product_iter<T, const N: usize, I>(
-    mut current: [T; N],
-    next_val_per_idx: &mut [I; N],
-    reset_per_idx: &[I; N],
-) -> Option<[T; N]>
+use std::{
+    array,
+    cmp::{Ordering, Reverse},
+    collections::{BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+    iter::FusedIterator,
+    ops::{Add, Sub},
+    vec,
+};
+
+#[cfg(feature = "rayon")]
+mod par;
+#[cfg(feature = "rayon")]
+pub use par::ParNDCartesianProduct;
+
+// A product's elements are numbered by treating the index into each axis as a digit, with the last axis the least significant.
+
+fn product_len(lens: &[usize]) -> usize {
+    lens.iter()
+        .try_fold(1usize, |acc, &len| acc.checked_mul(len))
+        .expect("Product should have at most usize::MAX elements")
+}
+
+/// Step to the next element. Wrapping past the last element is left to the caller to avoid.
+fn increment_digits(digits: &mut [usize], lens: &[usize]) {
+    for i in (0..digits.len()).rev() {
+        digits[i] += 1;
+        if digits[i] < lens[i] {
+            return;
+        }
+        digits[i] = 0;
+    }
+}
+
+/// Step to the previous element. Wrapping past the first element is left to the caller to avoid.
+fn decrement_digits(digits: &mut [usize], lens: &[usize]) {
+    for i in (0..digits.len()).rev() {
+        if digits[i] > 0 {
+            digits[i] -= 1;
+            return;
+        }
+        digits[i] = lens[i] - 1;
+    }
+}
+
+/// Position of the element with these digits.
+fn digits_index(digits: &[usize], lens: &[usize]) -> usize {
+    (0..digits.len()).fold(0, |acc, i| acc * lens[i] + digits[i])
+}
+
+/// Set `digits` to those of the element at `index`. Every axis must be non-empty.
+fn index_digits(mut index: usize, digits: &mut [usize], lens: &[usize]) {
+    for i in (0..digits.len()).rev() {
+        digits[i] = index % lens[i];
+        index /= lens[i];
+    }
+}
+
+/// One end of an [`NDCartesianProduct`]. Each axis is kept part way through so stepping only advances the axes whose digit changes.
+#[derive(Clone, Debug)]
+struct Cursor<I, T, const N: usize> {
+    digits: [usize; N],
+    /// What's left of each axis past its current value, in the direction the cursor moves.
+    rest: [I; N],
+    /// Value of each axis at its digit. [`None`] only if the axis is empty.
+    values: [Option<T>; N],
+}
+
+impl<I, const N: usize> Cursor<I, I::Item, N>
 where
-    I: Iterator<Item = T> + Clone,
+    I: Iterator + Clone,
 {
-    // Start at least significant digit first.
-    for i in (0..N).rev() {
-        // If still new values for idx get next and return.
-        if let Some(next) = next_val_per_idx[i].next() {
-            current[i] = next;
-            return Some(current);
+    fn first(axes: &[I; N]) -> Self {
+        let mut rest = axes.clone();
+        let values = array::from_fn(|i| rest[i].next());
+        Cursor {
+            digits: [0; N],
+            rest,
+            values,
         }
-        // If still more to check reset it and try next
-        else if i > 0 {
-            next_val_per_idx[i] = reset_per_idx[i].clone();
-            current[i] = next_val_per_idx[i].next().expect("Already reset iterator");
+    }
+
+    fn last(axes: &[I; N], lens: &[usize; N]) -> Self
+    where
+        I: DoubleEndedIterator,
+    {
+        let mut rest = axes.clone();
+        let values = array::from_fn(|i| rest[i].next_back());
+        Cursor {
+            digits: lens.map(|len| len.saturating_sub(1)),
+            rest,
+            values,
         }
     }
-    // If no more to check and all are at max then there is no more.
-    None
+
+    fn get(&self) -> [I::Item; N]
+    where
+        I::Item: Clone,
+    {
+        array::from_fn(|i| {
+            self.values[i]
+                .clone()
+                .expect("Axis should have as many values as its len")
+        })
+    }
+
+    fn step_forward(&mut self, axes: &[I; N], lens: &[usize; N]) {
+        for i in (0..N).rev() {
+            self.digits[i] += 1;
+            if self.digits[i] < lens[i] {
+                self.values[i] = self.rest[i].next();
+                return;
+            }
+            self.digits[i] = 0;
+            self.rest[i] = axes[i].clone();
+            self.values[i] = self.rest[i].next();
+        }
+    }
+
+    fn step_backward(&mut self, axes: &[I; N], lens: &[usize; N])
+    where
+        I: DoubleEndedIterator,
+    {
+        for i in (0..N).rev() {
+            if self.digits[i] > 0 {
+                self.digits[i] -= 1;
+                self.values[i] = self.rest[i].next_back();
+                return;
+            }
+            self.digits[i] = lens[i] - 1;
+            self.rest[i] = axes[i].clone();
+            self.values[i] = self.rest[i].next_back();
+        }
+    }
+
+    /// Move a front cursor to the element at `index`. An axis only starts over if its digit went down.
+    fn seek_forward(&mut self, index: usize, axes: &[I; N], lens: &[usize; N]) {
+        let mut digits = [0; N];
+        index_digits(index, &mut digits, lens);
+        for i in 0..N {
+            match digits[i].cmp(&self.digits[i]) {
+                Ordering::Less => {
+                    self.rest[i] = axes[i].clone();
+                    self.values[i] = self.rest[i].nth(digits[i]);
+                }
+                Ordering::Equal => {}
+                Ordering::Greater => {
+                    self.values[i] = self.rest[i].nth(digits[i] - self.digits[i] - 1);
+                }
+            }
+        }
+        self.digits = digits;
+    }
+
+    /// Move a back cursor to the element at `index`. An axis only starts over if its digit went up.
+    fn seek_backward(&mut self, index: usize, axes: &[I; N], lens: &[usize; N])
+    where
+        I: DoubleEndedIterator,
+    {
+        let mut digits = [0; N];
+        index_digits(index, &mut digits, lens);
+        for i in 0..N {
+            match digits[i].cmp(&self.digits[i]) {
+                Ordering::Less => {
+                    self.values[i] = self.rest[i].nth_back(self.digits[i] - digits[i] - 1);
+                }
+                Ordering::Equal => {}
+                Ordering::Greater => {
+                    self.rest[i] = axes[i].clone();
+                    self.values[i] = self.rest[i].nth_back(lens[i] - 1 - digits[i]);
+                }
+            }
+        }
+        self.digits = digits;
+    }
 }
 
 /// Cartesian product in lexicographical order over N iterators.
+///
+/// Axes are cloned and stepped as the product is iterated rather than collected.
+/// Each axis' length is found up front from its [`Iterator::size_hint`], or by counting a clone of it if the hint isn't exact,
+/// so axes must be finite. An element's position is then a mixed-radix number with one digit per axis,
+/// which lets [`Iterator::nth`] skip ahead and lets the product be iterated from both ends.
+/// [`NDCartesianProduct::buffered`] collects axes that can't be cloned or are slow to run again.
+/// See [`DynCartesianProduct`] for when the number of axes isn't known at compile time.
+/// If any axis is empty so is the product.
+/// ```
+/// # use advent_lib::iters::NDCartesianProduct;
+/// let mut product = NDCartesianProduct::new([0..2, 0..3]);
+/// assert_eq!(product.len(), 6);
+/// assert_eq!(product.next(), Some([0, 0]));
+/// assert_eq!(product.next_back(), Some([1, 2]));
+/// assert_eq!(product.nth(2), Some([1, 0]));
+/// assert_eq!(product.collect::<Vec<_>>(), vec![[1, 1]]);
+/// ```
 #[must_use = "iterators are lazy and do nothing unless consumed"]
 #[derive(Clone, Debug)]
 pub struct NDCartesianProduct<I, const N: usize>
 where
     I: Iterator,
 {
-    original_iters: [I; N],
-    next_val_iters: [I; N],
-    current: [I::Item; N],
+    /// Each axis from its start, cloned whenever an axis starts over.
+    axes: [I; N],
+    lens: [usize; N],
+    front: Cursor<I, I::Item, N>,
+    /// Only made on the first step from the back, so iterating forwards doesn't need [`DoubleEndedIterator`] axes.
+    back: Option<Cursor<I, I::Item, N>>,
+    /// Number of elements between the front and back (inclusive).
+    remaining: usize,
 }
 
 impl<I, const N: usize> NDCartesianProduct<I, N>
 where
     I: Iterator + Clone,
 {
-    pub fn new(mut values_per_axis: [I; N]) -> Self {
-        let original_iters = values_per_axis.clone();
-        // The length of current is N and so is values per axis. This unwrap should thus never fail unless an empty iterator is used.
-        // The values_per_axis are purposefully stepped here so that the lower bound is not repeated.
-        let current = array::from_fn(|i| {
-            values_per_axis[i]
-                .next()
-                .expect("All values per axis should have at least 1 valid value.")
+    /// ```
+    /// # use advent_lib::iters::NDCartesianProduct;
+    /// let evens = |len| (0..len).filter(|x| x % 2 == 0);
+    /// let product: Vec<_> = NDCartesianProduct::new([evens(5), evens(3)]).collect();
+    /// assert_eq!(product, [[0, 0], [0, 2], [2, 0], [2, 2], [4, 0], [4, 2]]);
+    /// assert_eq!(NDCartesianProduct::new([-1..=1i64, -1..=1, -1..=1]).len(), 27);
+    /// ```
+    pub fn new(axes: [I; N]) -> Self {
+        let lens = axes.each_ref().map(|axis| match axis.size_hint() {
+            (low, Some(high)) if low == high => low,
+            _ => axis.clone().count(),
         });
+        NDCartesianProduct {
+            front: Cursor::first(&axes),
+            back: None,
+            remaining: product_len(&lens),
+            axes,
+            lens,
+        }
+    }
 
-        // Reset the least significant idx (0) so the first element is not skipped
-        if let (Some(x), Some(y)) = (values_per_axis.last_mut(), original_iters.last()) {
-            *x = y.clone();
+    /// Position of the front among all the product's elements.
+    fn front_index(&self) -> usize {
+        if self.remaining == 0 {
+            0
+        } else {
+            digits_index(&self.front.digits, &self.lens)
         }
+    }
 
-        NDCartesianProduct {
-            original_iters,
-            next_val_iters: values_per_axis,
-            current,
+    /// The next `index` elements and the ones after them as two products.
+    #[cfg(feature = "rayon")]
+    fn split_at(self, index: usize) -> (Self, Self)
+    where
+        I::Item: Clone,
+    {
+        let mut right = self.clone();
+        if index < self.remaining {
+            let start = self.front_index() + index;
+            right.front.seek_forward(start, &self.axes, &self.lens);
         }
+        right.remaining = self.remaining - index;
+        let mut left = self;
+        left.remaining = index;
+        left.back = None;
+        (left, right)
+    }
+
+    /// Back cursor along with the axes to step it with, making it at the last remaining element if needed.
+    fn back_parts(&mut self) -> (&mut Cursor<I, I::Item, N>, &[I; N], &[usize; N])
+    where
+        I: DoubleEndedIterator,
+    {
+        let last = self.front_index() + self.remaining - 1;
+        let (axes, lens) = (&self.axes, &self.lens);
+        let back = self.back.get_or_insert_with(|| {
+            let mut cursor = Cursor::last(axes, lens);
+            cursor.seek_backward(last, axes, lens);
+            cursor
+        });
+        (back, axes, lens)
     }
 }
 
-impl<I: Iterator + Clone, const N: usize> Iterator for NDCartesianProduct<I, N>
+impl<T, const N: usize> NDCartesianProduct<vec::IntoIter<T>, N>
+where
+    T: Clone,
+{
+    /// Product of axes that can't be cloned, collecting each of them first.
+    /// Axes that never end never finish collecting.
+    /// ```
+    /// # use advent_lib::iters::NDCartesianProduct;
+    /// let axes = [0, 1].map(|x| (x..3).filter(|y| y % 2 == 0));
+    /// let product: Vec<_> = NDCartesianProduct::buffered(axes).collect();
+    /// assert_eq!(product, [[0, 2], [2, 2]]);
+    /// ```
+    pub fn buffered(axes: [impl IntoIterator<Item = T>; N]) -> Self {
+        Self::new(axes.map(|axis| axis.into_iter().collect::<Vec<_>>().into_iter()))
+    }
+}
+
+impl<I, const N: usize> Iterator for NDCartesianProduct<I, N>
 where
     I: Iterator + Clone,
     I::Item: Clone,
@@ -73,11 +302,1021 @@ where
     type Item = [I::Item; N];
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.current = next_product_iter(
-            self.current.clone(),
-            &mut self.next_val_iters,
-            &self.original_iters,
-        )?;
-        Some(self.current.clone())
+        if self.remaining == 0 {
+            return None;
+        }
+        let out = self.front.get();
+        self.remaining -= 1;
+        if self.remaining > 0 {
+            self.front.step_forward(&self.axes, &self.lens);
+        }
+        Some(out)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if n >= self.remaining {
+            self.remaining = 0;
+            return None;
+        }
+        let index = self.front_index() + n;
+        self.front.seek_forward(index, &self.axes, &self.lens);
+        self.remaining -= n;
+        self.next()
+    }
+}
+
+impl<I, const N: usize> DoubleEndedIterator for NDCartesianProduct<I, N>
+where
+    I: DoubleEndedIterator + Clone,
+    I::Item: Clone,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let more = self.remaining > 1;
+        let (back, axes, lens) = self.back_parts();
+        let out = back.get();
+        if more {
+            back.step_backward(axes, lens);
+        }
+        self.remaining -= 1;
+        Some(out)
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        if n >= self.remaining {
+            self.remaining = 0;
+            return None;
+        }
+        let index = self.front_index() + self.remaining - 1 - n;
+        let (back, axes, lens) = self.back_parts();
+        back.seek_backward(index, axes, lens);
+        self.remaining -= n;
+        self.next_back()
+    }
+}
+
+impl<I, const N: usize> ExactSizeIterator for NDCartesianProduct<I, N>
+where
+    I: Iterator + Clone,
+    I::Item: Clone,
+{
+}
+
+impl<I, const N: usize> FusedIterator for NDCartesianProduct<I, N>
+where
+    I: Iterator + Clone,
+    I::Item: Clone,
+{
+}
+
+/// [`NDCartesianProduct`] with the number of axes decided at runtime. Yields a [`Vec`] with a value from each axis in the same lexicographical order.
+/// ```
+/// # use advent_lib::iters::DynCartesianProduct;
+/// let axes = vec![vec!['a', 'b'], vec!['x'], vec!['y', 'z']];
+/// let product: Vec<String> = DynCartesianProduct::new(axes)
+///     .map(|x| x.into_iter().collect())
+///     .collect();
+/// assert_eq!(product, ["axy", "axz", "bxy", "bxz"]);
+/// ```
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[derive(Clone, Debug)]
+pub struct DynCartesianProduct<T> {
+    values_per_axis: Vec<Vec<T>>,
+    lens: Vec<usize>,
+    front: Vec<usize>,
+    back: Vec<usize>,
+    remaining: usize,
+}
+
+impl<T> DynCartesianProduct<T> {
+    pub fn new<I: IntoIterator<Item = T>>(values_per_axis: impl IntoIterator<Item = I>) -> Self {
+        let values_per_axis: Vec<Vec<T>> = values_per_axis
+            .into_iter()
+            .map(|x| x.into_iter().collect())
+            .collect();
+        let lens: Vec<usize> = values_per_axis.iter().map(Vec::len).collect();
+        DynCartesianProduct {
+            front: vec![0; lens.len()],
+            back: lens.iter().map(|len| len.saturating_sub(1)).collect(),
+            remaining: product_len(&lens),
+            values_per_axis,
+            lens,
+        }
+    }
+
+    fn get(&self, digits: &[usize]) -> Vec<T>
+    where
+        T: Clone,
+    {
+        digits
+            .iter()
+            .zip(&self.values_per_axis)
+            .map(|(&digit, values)| values[digit].clone())
+            .collect()
+    }
+}
+
+impl<T: Clone> Iterator for DynCartesianProduct<T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let out = self.get(&self.front);
+        self.remaining -= 1;
+        increment_digits(&mut self.front, &self.lens);
+        Some(out)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if n >= self.remaining {
+            self.remaining = 0;
+            return None;
+        }
+        let index = digits_index(&self.front, &self.lens) + n;
+        index_digits(index, &mut self.front, &self.lens);
+        self.remaining -= n;
+        self.next()
+    }
+}
+
+impl<T: Clone> DoubleEndedIterator for DynCartesianProduct<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let out = self.get(&self.back);
+        self.remaining -= 1;
+        decrement_digits(&mut self.back, &self.lens);
+        Some(out)
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        if n >= self.remaining {
+            self.remaining = 0;
+            return None;
+        }
+        let index = digits_index(&self.back, &self.lens) - n;
+        index_digits(index, &mut self.back, &self.lens);
+        self.remaining -= n;
+        self.next_back()
+    }
+}
+
+impl<T: Clone> ExactSizeIterator for DynCartesianProduct<T> {}
+
+impl<T: Clone> FusedIterator for DynCartesianProduct<T> {}
+
+/// Number of ways to choose `k` of `n` items. [`None`] if it doesn't fit in a [`usize`].
+fn binomial(n: usize, k: usize) -> Option<usize> {
+    if k > n {
+        return Some(0);
+    }
+    let k = k.min(n - k);
+    // Each partial product is itself a binomial so the division is exact.
+    (0..k)
+        .try_fold(1u128, |acc, i| {
+            Some(acc.checked_mul((n - i) as u128)? / (i as u128 + 1))
+        })
+        .and_then(|x| x.try_into().ok())
+}
+
+/// Rearrange into the next larger permutation in lexicographical order. Returns `false` and leaves the items unchanged if they're already the largest.
+fn next_permutation<T: Ord>(items: &mut [T]) -> bool {
+    let Some(pivot) = (1..items.len()).rev().find(|&i| items[i - 1] < items[i]) else {
+        return false;
+    };
+    let swap = (pivot..items.len())
+        .rev()
+        .find(|&i| items[pivot - 1] < items[i])
+        .expect("The item after the pivot is larger");
+    items.swap(pivot - 1, swap);
+    items[pivot..].reverse();
+    true
+}
+
+/// Size hint from a count that may have been too large to compute.
+fn count_hint(remaining: Option<usize>) -> (usize, Option<usize>) {
+    match remaining {
+        Some(x) => (x, Some(x)),
+        None => (usize::MAX, None),
+    }
+}
+
+/// Every way to choose `k` items from a pool, with the chosen items in pool order.
+/// Choices are in lexicographical order of their positions in the pool.
+/// ```
+/// # use advent_lib::iters::Combinations;
+/// let pairs: Vec<_> = Combinations::new("abc".chars(), 2).collect();
+/// assert_eq!(pairs, [['a', 'b'], ['a', 'c'], ['b', 'c']]);
+/// ```
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[derive(Clone, Debug)]
+pub struct Combinations<T> {
+    pool: Vec<T>,
+    /// Positions of the next choice.
+    next: Option<Vec<usize>>,
+    remaining: Option<usize>,
+}
+
+impl<T> Combinations<T> {
+    pub fn new(pool: impl IntoIterator<Item = T>, k: usize) -> Self {
+        let pool: Vec<T> = pool.into_iter().collect();
+        Combinations {
+            next: (k <= pool.len()).then(|| (0..k).collect()),
+            remaining: binomial(pool.len(), k),
+            pool,
+        }
+    }
+}
+
+impl<T: Clone> Iterator for Combinations<T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let indices = self.next.as_mut()?;
+        let out = indices.iter().map(|&i| self.pool[i].clone()).collect();
+        // Move the rightmost position that has room and pack the ones after it right behind it.
+        let (n, k) = (self.pool.len(), indices.len());
+        match (0..k).rev().find(|&i| indices[i] < n - k + i) {
+            Some(i) => {
+                indices[i] += 1;
+                for j in i + 1..k {
+                    indices[j] = indices[j - 1] + 1;
+                }
+            }
+            None => self.next = None,
+        }
+        self.remaining = self.remaining.map(|x| x - 1);
+        Some(out)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        count_hint(self.remaining)
+    }
+}
+
+impl<T: Clone> FusedIterator for Combinations<T> {}
+
+/// Every ordering of a pool in lexicographical order of positions in the pool.
+/// Equal items are treated as distinct, see [`MultisetPermutations`] to skip repeated orderings.
+/// ```
+/// # use advent_lib::iters::Permutations;
+/// let orders: Vec<String> = Permutations::new("abc".chars())
+///     .map(|x| x.into_iter().collect())
+///     .collect();
+/// assert_eq!(orders, ["abc", "acb", "bac", "bca", "cab", "cba"]);
+/// ```
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[derive(Clone, Debug)]
+pub struct Permutations<T> {
+    pool: Vec<T>,
+    /// Positions of the next ordering.
+    next: Option<Vec<usize>>,
+    remaining: Option<usize>,
+}
+
+impl<T> Permutations<T> {
+    pub fn new(pool: impl IntoIterator<Item = T>) -> Self {
+        let pool: Vec<T> = pool.into_iter().collect();
+        Permutations {
+            next: Some((0..pool.len()).collect()),
+            remaining: (1..=pool.len()).try_fold(1usize, usize::checked_mul),
+            pool,
+        }
+    }
+}
+
+impl<T: Clone> Iterator for Permutations<T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let indices = self.next.as_mut()?;
+        let out = indices.iter().map(|&i| self.pool[i].clone()).collect();
+        if !next_permutation(indices) {
+            self.next = None;
+        }
+        self.remaining = self.remaining.map(|x| x - 1);
+        Some(out)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        count_hint(self.remaining)
+    }
+}
+
+impl<T: Clone> FusedIterator for Permutations<T> {}
+
+/// Every distinct ordering of items that may repeat, in lexicographical order.
+/// ```
+/// # use advent_lib::iters::MultisetPermutations;
+/// let orders: Vec<String> = MultisetPermutations::new("baa".chars())
+///     .map(|x| x.into_iter().collect())
+///     .collect();
+/// assert_eq!(orders, ["aab", "aba", "baa"]);
+/// ```
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[derive(Clone, Debug)]
+pub struct MultisetPermutations<T> {
+    next: Option<Vec<T>>,
+    remaining: Option<usize>,
+}
+
+impl<T: Ord> MultisetPermutations<T> {
+    pub fn new(items: impl IntoIterator<Item = T>) -> Self {
+        let mut items: Vec<T> = items.into_iter().collect();
+        items.sort();
+        // Multinomial coefficient: choose where each run of equal items goes among the positions left.
+        let mut remaining = Some(1usize);
+        let mut placed = 0;
+        for run in items.chunk_by(|a, b| a == b) {
+            placed += run.len();
+            remaining = remaining
+                .zip(binomial(placed, run.len()))
+                .and_then(|(x, y)| x.checked_mul(y));
+        }
+        MultisetPermutations {
+            next: Some(items),
+            remaining,
+        }
+    }
+}
+
+impl<T: Ord + Clone> Iterator for MultisetPermutations<T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let items = self.next.as_mut()?;
+        let out = items.clone();
+        if !next_permutation(items) {
+            self.next = None;
+        }
+        self.remaining = self.remaining.map(|x| x - 1);
+        Some(out)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        count_hint(self.remaining)
+    }
+}
+
+impl<T: Ord + Clone> FusedIterator for MultisetPermutations<T> {}
+
+/// Every way to split `total` into an ordered list of `parts` numbers in lexicographical order.
+/// ```
+/// # use advent_lib::iters::Compositions;
+/// let splits: Vec<_> = Compositions::new(2, 2).collect();
+/// assert_eq!(splits, [[0, 2], [1, 1], [2, 0]]);
+/// let splits: Vec<_> = Compositions::positive(4, 3).collect();
+/// assert_eq!(splits, [[1, 1, 2], [1, 2, 1], [2, 1, 1]]);
+/// ```
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[derive(Clone, Debug)]
+pub struct Compositions {
+    /// Next split with `min` taken off of each part.
+    next: Option<Vec<usize>>,
+    min: usize,
+    remaining: Option<usize>,
+}
+
+impl Compositions {
+    /// Parts can be zero.
+    pub fn new(total: usize, parts: usize) -> Self {
+        Self::with_min(total, parts, 0)
+    }
+
+    /// Every part is at least one.
+    pub fn positive(total: usize, parts: usize) -> Self {
+        Self::with_min(total, parts, 1)
+    }
+
+    /// Every part is at least `min`.
+    pub fn with_min(total: usize, parts: usize, min: usize) -> Self {
+        let rest = parts.checked_mul(min).and_then(|x| total.checked_sub(x));
+        let next = match (rest, parts) {
+            (Some(rest), 1..) => {
+                let mut next = vec![0; parts];
+                next[parts - 1] = rest;
+                Some(next)
+            }
+            // Nothing split into no parts is the empty split.
+            (Some(0), 0) => Some(Vec::new()),
+            _ => None,
+        };
+        let remaining = match (rest, parts) {
+            (Some(rest), 1..) => rest
+                .checked_add(parts - 1)
+                .and_then(|n| binomial(n, parts - 1)),
+            _ => Some(next.is_some() as usize),
+        };
+        Compositions {
+            next,
+            min,
+            remaining,
+        }
+    }
+}
+
+impl Iterator for Compositions {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let parts = self.next.as_mut()?;
+        let out = parts.iter().map(|x| x + self.min).collect();
+        // Move one from everything after the rightmost part that can grow into it, with what's left in the last part.
+        let last = parts.len().saturating_sub(1);
+        match (0..last)
+            .rev()
+            .find(|&i| parts[i + 1..].iter().any(|&x| x > 0))
+        {
+            Some(i) => {
+                let rest = parts[i + 1..].iter().sum::<usize>() - 1;
+                parts[i] += 1;
+                parts[i + 1..].fill(0);
+                parts[last] = rest;
+            }
+            None => self.next = None,
+        }
+        self.remaining = self.remaining.map(|x| x - 1);
+        Some(out)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        count_hint(self.remaining)
+    }
+}
+
+impl FusedIterator for Compositions {}
+
+/// Start of the first run of `size` consecutive items that are all different, found in a single pass.
+/// Like [`slice::windows`] followed by [`Iterator::position`], but remembering where each item was last seen instead of rechecking every window.
+/// ```
+/// # use advent_lib::iters::first_distinct_window;
+/// assert_eq!(first_distinct_window("mjqjpqmgbljsphdztnvjfqwrcgsmlb".chars(), 4), Some(3));
+/// assert_eq!(first_distinct_window("aabb".chars(), 3), None);
+/// ```
+pub fn first_distinct_window<I>(items: I, size: usize) -> Option<usize>
+where
+    I: IntoIterator,
+    I::Item: Hash + Eq,
+{
+    if size == 0 {
+        return Some(0);
+    }
+    let mut last_seen = HashMap::new();
+    // Start of the longest run of distinct items ending at the current item.
+    let mut start = 0;
+    for (i, item) in items.into_iter().enumerate() {
+        if let Some(previous) = last_seen.insert(item, i) {
+            start = start.max(previous + 1);
+        }
+        if i + 1 - start == size {
+            return Some(start);
+        }
+    }
+    None
+}
+
+/// Size hint for rolling windows of `size` after `seen` items were taken from an iterator with `hint` left.
+fn windows_left(seen: usize, size: usize, hint: (usize, Option<usize>)) -> (usize, Option<usize>) {
+    let emitted = seen.saturating_sub(size - 1);
+    let windows = |left: usize| {
+        left.saturating_add(seen)
+            .saturating_sub(size - 1)
+            .saturating_sub(emitted)
+    };
+    (windows(hint.0), hint.1.map(windows))
+}
+
+/// Sum of each window of `size` consecutive items, updated by adding the newest item and subtracting the oldest.
+/// ```
+/// # use advent_lib::iters::RollingSum;
+/// let sums: Vec<_> = RollingSum::new([1, 2, 3, 4, 5], 3).collect();
+/// assert_eq!(sums, [6, 9, 12]);
+/// ```
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[derive(Clone, Debug)]
+pub struct RollingSum<I: Iterator> {
+    iter: I,
+    size: usize,
+    window: VecDeque<I::Item>,
+    sum: Option<I::Item>,
+    seen: usize,
+}
+
+impl<I: Iterator> RollingSum<I> {
+    /// Panics if `size` is 0.
+    pub fn new(iter: impl IntoIterator<IntoIter = I>, size: usize) -> Self {
+        assert!(size > 0, "Window size should be nonzero");
+        RollingSum {
+            iter: iter.into_iter(),
+            size,
+            window: VecDeque::with_capacity(size),
+            sum: None,
+            seen: 0,
+        }
+    }
+}
+
+impl<I> Iterator for RollingSum<I>
+where
+    I: Iterator,
+    I::Item: Add<Output = I::Item> + Sub<Output = I::Item> + Clone,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let sum = match &self.sum {
+            Some(sum) => {
+                let item = self.iter.next()?;
+                self.seen += 1;
+                let oldest = self.window.pop_front().expect("Window is full");
+                self.window.push_back(item.clone());
+                // Subtract first so unsigned sums don't overflow on the way.
+                sum.clone() - oldest + item
+            }
+            None => {
+                while self.window.len() < self.size {
+                    self.window.push_back(self.iter.next()?);
+                    self.seen += 1;
+                }
+                self.window
+                    .iter()
+                    .cloned()
+                    .reduce(|acc, x| acc + x)
+                    .expect("Window is nonempty")
+            }
+        };
+        self.sum = Some(sum.clone());
+        Some(sum)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        windows_left(self.seen, self.size, self.iter.size_hint())
+    }
+}
+
+/// Items of a window kept in a deque from most to least extreme, dropping any item that can't become the extreme before it leaves the window.
+#[derive(Clone, Debug)]
+struct MonotonicWindow<I, T> {
+    iter: I,
+    size: usize,
+    /// Kept items are ordered this way relative to every later kept item.
+    keep: Ordering,
+    window: VecDeque<(usize, T)>,
+    seen: usize,
+}
+
+impl<I: Iterator> MonotonicWindow<I, I::Item> {
+    fn new(iter: I, size: usize, keep: Ordering) -> Self {
+        assert!(size > 0, "Window size should be nonzero");
+        MonotonicWindow {
+            iter,
+            size,
+            keep,
+            window: VecDeque::new(),
+            seen: 0,
+        }
+    }
+}
+
+impl<I> Iterator for MonotonicWindow<I, I::Item>
+where
+    I: Iterator,
+    I::Item: Ord + Clone,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let item = self.iter.next()?;
+            while self
+                .window
+                .back()
+                .is_some_and(|(_, x)| x.cmp(&item) != self.keep)
+            {
+                self.window.pop_back();
+            }
+            self.window.push_back((self.seen, item));
+            self.seen += 1;
+            if self.seen >= self.size {
+                let start = self.seen - self.size;
+                while self.window.front().is_some_and(|&(i, _)| i < start) {
+                    self.window.pop_front();
+                }
+                return self.window.front().map(|(_, x)| x.clone());
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        windows_left(self.seen, self.size, self.iter.size_hint())
+    }
+}
+
+/// Smallest item of each window of `size` consecutive items in amortized constant time per window.
+/// ```
+/// # use advent_lib::iters::RollingMin;
+/// let mins: Vec<_> = RollingMin::new([4, 2, 12, 3, 8], 2).collect();
+/// assert_eq!(mins, [2, 2, 3, 3]);
+/// ```
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[derive(Clone, Debug)]
+pub struct RollingMin<I: Iterator>(MonotonicWindow<I, I::Item>);
+
+impl<I: Iterator> RollingMin<I> {
+    /// Panics if `size` is 0.
+    pub fn new(iter: impl IntoIterator<IntoIter = I>, size: usize) -> Self {
+        RollingMin(MonotonicWindow::new(iter.into_iter(), size, Ordering::Less))
+    }
+}
+
+impl<I> Iterator for RollingMin<I>
+where
+    I: Iterator,
+    I::Item: Ord + Clone,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+/// Largest item of each window of `size` consecutive items in amortized constant time per window.
+/// ```
+/// # use advent_lib::iters::RollingMax;
+/// let maxes: Vec<_> = RollingMax::new([4, 2, 12, 3, 8], 3).collect();
+/// assert_eq!(maxes, [12, 12, 12]);
+/// ```
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[derive(Clone, Debug)]
+pub struct RollingMax<I: Iterator>(MonotonicWindow<I, I::Item>);
+
+impl<I: Iterator> RollingMax<I> {
+    /// Panics if `size` is 0.
+    pub fn new(iter: impl IntoIterator<IntoIter = I>, size: usize) -> Self {
+        RollingMax(MonotonicWindow::new(
+            iter.into_iter(),
+            size,
+            Ordering::Greater,
+        ))
+    }
+}
+
+impl<I> Iterator for RollingMax<I>
+where
+    I: Iterator,
+    I::Item: Ord + Clone,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+/// The `k` largest items pushed so far. Only those `k` are stored so it can follow a stream of any length.
+/// ```
+/// # use advent_lib::iters::TopK;
+/// let mut top = TopK::new(3);
+/// top.extend([5, 1, 9, 3, 7]);
+/// assert_eq!(top.min(), Some(&5));
+/// assert_eq!(top.into_sorted_vec(), [9, 7, 5]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct TopK<T> {
+    k: usize,
+    /// Min-heap so the item to evict is on top.
+    heap: BinaryHeap<Reverse<T>>,
+}
+
+impl<T: Ord> TopK<T> {
+    pub fn new(k: usize) -> Self {
+        TopK {
+            k,
+            heap: BinaryHeap::new(),
+        }
+    }
+
+    /// Keep `item` if it's among the `k` largest. Items equal to the smallest kept item aren't kept once `k` items are kept.
+    pub fn push(&mut self, item: T) {
+        if self.heap.len() < self.k {
+            self.heap.push(Reverse(item));
+        } else if let Some(mut smallest) = self.heap.peek_mut() {
+            if item > smallest.0 {
+                *smallest = Reverse(item);
+            }
+        }
+    }
+
+    /// Smallest kept item. This is the `k`th largest once at least `k` items were pushed.
+    pub fn min(&self) -> Option<&T> {
+        self.heap.peek().map(|Reverse(x)| x)
+    }
+
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Kept items from largest to smallest.
+    pub fn into_sorted_vec(self) -> Vec<T> {
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse(x)| x)
+            .collect()
+    }
+}
+
+impl<T: Ord> Extend<T> for TopK<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.push(item);
+        }
+    }
+}
+
+/// The `k` largest items from largest to smallest. Shorthand for [`TopK`].
+/// ```
+/// # use advent_lib::iters::top_k;
+/// assert_eq!(top_k([3, 1, 4, 1, 5, 9, 2, 6], 3), [9, 6, 5]);
+/// ```
+pub fn top_k<T: Ord>(items: impl IntoIterator<Item = T>, k: usize) -> Vec<T> {
+    let mut top = TopK::new(k);
+    top.extend(items);
+    top.into_sorted_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use itertools::Itertools;
+
+    #[test]
+    fn product_test() {
+        let product = NDCartesianProduct::new([0..2, 5..8, 0..1]);
+        let expected: Vec<_> = (0..2)
+            .flat_map(|x| (5..8).map(move |y| [x, y, 0]))
+            .collect();
+        assert_eq!(product.len(), 6);
+        assert_eq!(product.clone().collect::<Vec<_>>(), expected);
+        let mut reversed = expected.clone();
+        reversed.reverse();
+        assert_eq!(product.clone().rev().collect::<Vec<_>>(), reversed);
+        for n in 0..8 {
+            assert_eq!(product.clone().nth(n), expected.get(n).copied());
+            assert_eq!(product.clone().nth_back(n), reversed.get(n).copied());
+        }
+    }
+
+    #[test]
+    fn product_both_ends_test() {
+        let mut product = NDCartesianProduct::new([0..3, 0..3]);
+        assert_eq!(product.nth(3), Some([1, 0]));
+        assert_eq!(product.nth_back(3), Some([1, 2]));
+        assert_eq!(product.len(), 1);
+        assert_eq!(product.next_back(), Some([1, 1]));
+        assert_eq!(product.next(), None);
+        assert_eq!(product.next_back(), None);
+    }
+
+    #[test]
+    fn product_mixed_steps_test() {
+        let product = NDCartesianProduct::new([0..3, 0..4, 0..2]);
+        let expected: Vec<_> = product.clone().collect();
+        for front in 0..25 {
+            for back in 0..25 {
+                let mut product = product.clone();
+                let first = product.nth(front);
+                let last = product.nth_back(back);
+                let rest = expected.get(front + 1..expected.len().saturating_sub(back + 1));
+                assert_eq!(first, expected.get(front).copied());
+                assert_eq!(last, rest.and(expected.iter().rev().nth(back).copied()));
+                assert_eq!(product.len(), rest.map_or(0, <[_]>::len));
+                assert_eq!(product.collect::<Vec<_>>(), rest.unwrap_or_default());
+            }
+        }
+    }
+
+    #[test]
+    fn huge_product_test() {
+        // Axes are stepped as needed rather than collected.
+        let mut product = NDCartesianProduct::new([0..usize::MAX / 4, 0..4]);
+        assert_eq!(product.len(), usize::MAX / 4 * 4);
+        assert_eq!(product.next(), Some([0, 0]));
+        assert_eq!(product.next_back(), Some([usize::MAX / 4 - 1, 3]));
+        let index = 1 + usize::MAX / 8;
+        assert_eq!(product.nth(usize::MAX / 8), Some([index / 4, index % 4]));
+    }
+
+    #[test]
+    fn inexact_product_test() {
+        // Axes without an exact size are counted, then stepped like any other.
+        let odd = |len: i64| (0..len).filter(|x| x % 2 == 1);
+        let product = NDCartesianProduct::new([odd(4), odd(6)]);
+        assert_eq!(product.len(), 6);
+        assert_eq!(product.clone().nth(4), Some([3, 3]));
+        assert_eq!(product.clone().next_back(), Some([3, 5]));
+        let wide = NDCartesianProduct::new([0..=i64::MAX / 2, 0..=1]);
+        assert_eq!(wide.clone().nth_back(1), Some([i64::MAX / 2, 0]));
+    }
+
+    #[test]
+    fn empty_product_test() {
+        let mut product = NDCartesianProduct::new([0..3, 0..0, 0..2]);
+        assert_eq!(product.len(), 0);
+        assert_eq!(product.next(), None);
+        assert_eq!(product.next_back(), None);
+        assert_eq!(product.nth(2), None);
+        // No axes has exactly one product, the empty one.
+        let mut product = NDCartesianProduct::<std::ops::Range<i32>, 0>::new([]);
+        assert_eq!(product.next(), Some([]));
+        assert_eq!(product.next(), None);
+    }
+
+    #[test]
+    fn dyn_product_test() {
+        let axes = [vec![0, 1], vec![5, 6, 7], vec![0]];
+        let expected: Vec<_> = NDCartesianProduct::new(axes.clone().map(Vec::into_iter))
+            .map(Vec::from)
+            .collect();
+        let product = DynCartesianProduct::new(axes);
+        assert_eq!(product.len(), 6);
+        assert_eq!(product.clone().collect::<Vec<_>>(), expected);
+        assert!(product.clone().rev().eq(expected.iter().rev().cloned()));
+        for n in 0..7 {
+            assert_eq!(product.clone().nth(n), expected.get(n).cloned());
+            assert_eq!(
+                product.clone().nth_back(n),
+                expected.iter().rev().nth(n).cloned()
+            );
+        }
+        assert_eq!(DynCartesianProduct::new([vec![1], vec![]]).next(), None);
+        let mut product = DynCartesianProduct::<i32>::new(Vec::<Vec<_>>::new());
+        assert_eq!(product.next(), Some(vec![]));
+        assert_eq!(product.next(), None);
+    }
+
+    #[test]
+    fn combinations_test() {
+        for n in 0..6 {
+            for k in 0..7 {
+                let combinations = Combinations::new(0..n, k);
+                let expected: Vec<_> = (0..n).combinations(k).collect();
+                assert_eq!(combinations.size_hint().1, Some(expected.len()));
+                assert_eq!(combinations.collect::<Vec<_>>(), expected);
+            }
+        }
+        assert_eq!(binomial(70, 35), None);
+        assert_eq!(binomial(60, 30), Some(118264581564861424));
+    }
+
+    #[test]
+    fn permutations_test() {
+        for n in 0..6 {
+            let permutations = Permutations::new(0..n);
+            let expected: Vec<_> = (0..n).permutations(n).collect();
+            assert_eq!(permutations.size_hint().1, Some(expected.len()));
+            assert_eq!(permutations.collect::<Vec<_>>(), expected);
+        }
+        assert_eq!(Permutations::new(0..30).size_hint(), (usize::MAX, None));
+    }
+
+    #[test]
+    fn multiset_permutations_test() {
+        let items = [3, 1, 3, 2, 1, 3];
+        let mut expected: Vec<_> = items.iter().copied().permutations(items.len()).collect();
+        expected.sort();
+        expected.dedup();
+        let permutations = MultisetPermutations::new(items);
+        assert_eq!(permutations.size_hint().1, Some(expected.len()));
+        assert_eq!(permutations.collect::<Vec<_>>(), expected);
+        assert_eq!(
+            MultisetPermutations::new([0; 0]).collect::<Vec<_>>(),
+            vec![Vec::<i32>::new()]
+        );
+    }
+
+    #[test]
+    fn compositions_test() {
+        for total in 0..6 {
+            for parts in 0..5 {
+                let compositions = Compositions::new(total, parts);
+                let expected: Vec<_> = DynCartesianProduct::new(vec![0..=total; parts])
+                    .filter(|x| x.iter().sum::<usize>() == total)
+                    .collect();
+                assert_eq!(compositions.size_hint().1, Some(expected.len()));
+                assert_eq!(compositions.collect::<Vec<_>>(), expected);
+
+                let positive: Vec<_> = expected
+                    .iter()
+                    .filter(|x| x.iter().all(|&x| x >= 2))
+                    .cloned()
+                    .collect();
+                let compositions = Compositions::with_min(total, parts, 2);
+                assert_eq!(compositions.size_hint().1, Some(positive.len()));
+                assert_eq!(compositions.collect::<Vec<_>>(), positive);
+            }
+        }
+    }
+
+    #[test]
+    fn first_distinct_window_test() {
+        let naive =
+            |items: &[u8], size: usize| items.windows(size).position(|w| w.iter().all_unique());
+        for (input, four, fourteen) in [
+            ("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 7, 19),
+            ("bvwbjplbgvbhsrlpgdmjqwftvncz", 5, 23),
+            ("nppdvjthqldpwncqszvftbrmjlhg", 6, 23),
+            ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 10, 29),
+            ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 11, 26),
+        ] {
+            let bytes = input.as_bytes();
+            assert_eq!(first_distinct_window(bytes, 4), Some(four - 4));
+            assert_eq!(first_distinct_window(bytes, 14), Some(fourteen - 14));
+            for size in 1..=bytes.len() + 1 {
+                assert_eq!(first_distinct_window(bytes, size), naive(bytes, size));
+            }
+        }
+        assert_eq!(first_distinct_window([0; 0], 0), Some(0));
+        assert_eq!(first_distinct_window([0; 0], 1), None);
+    }
+
+    #[test]
+    fn rolling_test() {
+        let items = [5u32, 1, 4, 4, 0, 9, 2, 6, 6, 3, 8];
+        for size in 1..=items.len() + 1 {
+            let sums = RollingSum::new(items, size);
+            let mins = RollingMin::new(items, size);
+            let maxes = RollingMax::new(items, size);
+            let windows = items.len().saturating_sub(size - 1);
+            assert_eq!(sums.size_hint(), (windows, Some(windows)));
+            assert_eq!(mins.size_hint(), (windows, Some(windows)));
+            assert_eq!(
+                sums.collect::<Vec<_>>(),
+                items
+                    .windows(size)
+                    .map(|w| w.iter().sum())
+                    .collect::<Vec<u32>>()
+            );
+            assert_eq!(
+                mins.collect::<Vec<_>>(),
+                items
+                    .windows(size)
+                    .map(|w| *w.iter().min().unwrap())
+                    .collect::<Vec<_>>()
+            );
+            assert_eq!(
+                maxes.collect::<Vec<_>>(),
+                items
+                    .windows(size)
+                    .map(|w| *w.iter().max().unwrap())
+                    .collect::<Vec<_>>()
+            );
+        }
+        let mut sums = RollingSum::new(items, 3);
+        sums.nth(2);
+        assert_eq!(sums.size_hint(), (6, Some(6)));
+    }
+
+    #[test]
+    fn top_k_test() {
+        let items = [3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5];
+        for k in 0..=items.len() + 1 {
+            let mut sorted = items.to_vec();
+            sorted.sort_by_key(|&x| Reverse(x));
+            sorted.truncate(k);
+            assert_eq!(top_k(items, k), sorted);
+        }
+        let mut top = TopK::new(2);
+        assert!(top.is_empty());
+        assert_eq!(top.min(), None);
+        top.extend((0..1_000_000).map(|x| x % 1000));
+        assert_eq!(top.len(), 2);
+        assert_eq!(top.into_sorted_vec(), [999, 999]);
     }
 }
@@ -0,0 +1,188 @@
+use super::NDCartesianProduct;
+use rayon::iter::{
+    plumbing::{bridge, Consumer, Producer, ProducerCallback, UnindexedConsumer},
+    IndexedParallelIterator, IntoParallelIterator, ParallelIterator,
+};
+use std::fmt::{self, Debug};
+
+/// Parallel version of [`NDCartesianProduct`] made with [`IntoParallelIterator::into_par_iter`].
+///
+/// Work is split by element index so each thread seeks to the start of its own chunk and steps through it like the serial product.
+/// Being indexed, order is kept by [`ParallelIterator::collect`] and the `*_first` methods,
+/// and ties in methods like [`ParallelIterator::min_by_key`] go to the earliest element, so results don't depend on scheduling.
+/// ```
+/// # use advent_lib::iters::NDCartesianProduct;
+/// use rayon::prelude::*;
+/// let product = NDCartesianProduct::new([0..3, 0..4]);
+/// let sums: Vec<_> = product.clone().into_par_iter().map(|[x, y]| x + y).collect();
+/// assert_eq!(sums, product.map(|[x, y]| x + y).collect::<Vec<_>>());
+/// ```
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct ParNDCartesianProduct<I, const N: usize>
+where
+    I: Iterator,
+{
+    product: NDCartesianProduct<I, N>,
+}
+
+impl<I, const N: usize> Clone for ParNDCartesianProduct<I, N>
+where
+    I: Iterator,
+    NDCartesianProduct<I, N>: Clone,
+{
+    fn clone(&self) -> Self {
+        ParNDCartesianProduct {
+            product: self.product.clone(),
+        }
+    }
+}
+
+impl<I, const N: usize> Debug for ParNDCartesianProduct<I, N>
+where
+    I: Iterator,
+    NDCartesianProduct<I, N>: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ParNDCartesianProduct")
+            .field("product", &self.product)
+            .finish()
+    }
+}
+
+impl<I, const N: usize> IntoParallelIterator for NDCartesianProduct<I, N>
+where
+    I: DoubleEndedIterator + Clone + Send,
+    I::Item: Clone + Send,
+{
+    type Iter = ParNDCartesianProduct<I, N>;
+    type Item = [I::Item; N];
+
+    /// Elements not yet taken from either end of the product.
+    fn into_par_iter(self) -> Self::Iter {
+        ParNDCartesianProduct { product: self }
+    }
+}
+
+impl<I, const N: usize> ParallelIterator for ParNDCartesianProduct<I, N>
+where
+    I: DoubleEndedIterator + Clone + Send,
+    I::Item: Clone + Send,
+{
+    type Item = [I::Item; N];
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        bridge(self, consumer)
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        Some(self.product.len())
+    }
+}
+
+impl<I, const N: usize> IndexedParallelIterator for ParNDCartesianProduct<I, N>
+where
+    I: DoubleEndedIterator + Clone + Send,
+    I::Item: Clone + Send,
+{
+    fn len(&self) -> usize {
+        self.product.len()
+    }
+
+    fn drive<C: Consumer<Self::Item>>(self, consumer: C) -> C::Result {
+        bridge(self, consumer)
+    }
+
+    fn with_producer<CB: ProducerCallback<Self::Item>>(self, callback: CB) -> CB::Output {
+        callback.callback(ProductChunk(self.product))
+    }
+}
+
+/// Contiguous run of a product's elements handed to one thread, itself a product cut down at both ends.
+struct ProductChunk<I, const N: usize>(NDCartesianProduct<I, N>)
+where
+    I: Iterator;
+
+impl<I, const N: usize> Producer for ProductChunk<I, N>
+where
+    I: DoubleEndedIterator + Clone + Send,
+    I::Item: Clone + Send,
+{
+    type Item = [I::Item; N];
+    type IntoIter = NDCartesianProduct<I, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0
+    }
+
+    fn split_at(self, index: usize) -> (Self, Self) {
+        let (left, right) = self.0.split_at(index);
+        (ProductChunk(left), ProductChunk(right))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn par_product_test() {
+        let product = NDCartesianProduct::new([0..7, 0..5, 0..3]);
+        assert_eq!(
+            product.clone().into_par_iter().collect::<Vec<_>>(),
+            product.clone().collect::<Vec<_>>()
+        );
+        assert_eq!(product.clone().into_par_iter().len(), 105);
+        // Ties go to the first element in order.
+        assert_eq!(
+            product
+                .clone()
+                .into_par_iter()
+                .min_by_key(|[x, y, _]| x + y),
+            Some([0, 0, 0])
+        );
+        assert_eq!(
+            product
+                .clone()
+                .into_par_iter()
+                .max_by_key(|[x, y, _]| x + y),
+            Some([6, 4, 2])
+        );
+        assert_eq!(
+            product
+                .clone()
+                .into_par_iter()
+                .rev()
+                .take(2)
+                .collect::<Vec<_>>(),
+            vec![[6, 4, 2], [6, 4, 1]]
+        );
+
+        // Only what's left of a partly used product.
+        let mut partial = product.clone();
+        partial.nth(10);
+        partial.nth_back(20);
+        assert_eq!(
+            partial.clone().into_par_iter().collect::<Vec<_>>(),
+            partial.collect::<Vec<_>>()
+        );
+
+        let mut used = NDCartesianProduct::new([0..2, 0..2]);
+        used.by_ref().for_each(drop);
+        assert_eq!(used.into_par_iter().count(), 0);
+        assert_eq!(
+            NDCartesianProduct::new([0..2, 0..0])
+                .into_par_iter()
+                .count(),
+            0
+        );
+        assert_eq!(
+            NDCartesianProduct::<std::ops::Range<i32>, 0>::new([])
+                .into_par_iter()
+                .collect::<Vec<_>>(),
+            vec![[0i32; 0]]
+        );
+    }
+}
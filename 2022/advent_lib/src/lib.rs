@@ -1,7 +1,47 @@
 pub mod algorithms;
 pub mod cord;
+pub mod cube_net;
 pub mod dir;
+pub mod grid;
+pub mod hex;
 pub mod iters;
+pub mod manhattan;
 pub mod parse;
+pub mod symmetry;
+pub mod topology;
+pub mod turtle;
 // Macros are exported to root.
 mod macros;
+mod offsets;
+
+// Lets `scan!` refer to `::advent_lib` from inside this crate.
+extern crate self as advent_lib;
+
+/// Match a line against a pattern and parse the captured parts, failing with a [`ParseError`](parse::ParseError).
+///
+/// The pattern is checked when compiling and the output has one value per capture, as a tuple if there's more than one.
+/// - Text matches exactly.
+/// - Whitespace matches one or more whitespace characters, or none at the start or end of the input.
+/// - `{_ws}` matches any amount of whitespace, including none.
+/// - `{}` or `{T}` parses the text up to the next whitespace or text in the pattern with [`FromStr`](std::str::FromStr).
+///   It fails if there's no text to parse.
+///   Without a type it's inferred from how the output is used.
+/// - `{[T]sep}` parses a [`Vec`] of items separated by `sep`, or by whitespace if `sep` is empty.
+///   It runs up to the next text in the pattern.
+/// - `{{` and `}}` match a literal `{` and `}`.
+/// ```
+/// # use advent_lib::scan;
+/// let (amount, from, to): (usize, usize, usize) = scan!("move 3 from 1 to 2", "move {} from {} to {}")?;
+/// assert_eq!((amount, from, to), (3, 1, 2));
+/// let (id, items) = scan!("Monkey 0:  items: 79, 98", "Monkey {u8}: items: {[u64],}")?;
+/// assert_eq!((id, items), (0, vec![79, 98]));
+/// let ranges: (u32, u32, u32, u32) = scan!("2-4,6-8", "{}-{},{}-{}")?;
+/// assert_eq!(ranges, (2, 4, 6, 8));
+/// let pair: (i8, i8) = scan!("(1 ,-2)", "({}{_ws},{_ws}{})")?;
+/// assert_eq!(pair, (1, -2));
+///
+/// let err = scan!("move x from 1 to 2", "move {usize} from {usize} to {usize}").unwrap_err();
+/// assert_eq!((err.column, err.expected.as_str()), (6, "usize"));
+/// # Ok::<(), advent_lib::parse::ParseError>(())
+/// ```
+pub use advent_macros::scan;
@@ -0,0 +1,341 @@
+use crate::{cord::NDCord, iters::NDCartesianProduct};
+use num_traits::{cast, PrimInt, Signed};
+
+/// Rotate 45 degrees into the frame `[x + y, x - y]` where Manhattan distance becomes Chebyshev distance.
+/// ```
+/// # use advent_lib::{cord::NDCord, manhattan::to_rotated};
+/// assert_eq!(to_rotated(NDCord([3, 1])), NDCord([4, 2]));
+/// ```
+pub fn to_rotated<T: PrimInt + Signed>(cord: NDCord<T, 2>) -> NDCord<T, 2> {
+    NDCord([cord[0] + cord[1], cord[0] - cord[1]])
+}
+
+/// Inverse of [`to_rotated`]. [`None`] when the rotated cordinate is between cells, which is when its axes have different parity.
+pub fn from_rotated<T: PrimInt + Signed>(cord: NDCord<T, 2>) -> Option<NDCord<T, 2>> {
+    let two = T::one() + T::one();
+    let (sum, diff) = (cord[0] + cord[1], cord[0] - cord[1]);
+    (sum % two)
+        .is_zero()
+        .then(|| NDCord([sum / two, diff / two]))
+}
+
+fn widen<T: PrimInt>(x: T) -> i128 {
+    x.to_i128().expect("Cord fits in i128")
+}
+
+fn narrow<T: PrimInt>(x: i128) -> T {
+    cast(x).expect("Result fits in cord's datatype")
+}
+
+fn distance<T: PrimInt + Signed>(a: &NDCord<T, 2>, b: &NDCord<T, 2>) -> T {
+    (a[0] - b[0]).abs() + (a[1] - b[1]).abs()
+}
+
+/// Number of integers in `start..end` with the given parity.
+fn count_parity(start: i128, end: i128, parity: i128) -> i128 {
+    if start >= end {
+        return 0;
+    }
+    (end - 1 - parity).div_euclid(2) - (start - 1 - parity).div_euclid(2)
+}
+
+/// All cells within a Manhattan distance of `radius` from `center`. A diamond shape in the grid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ManhattanBall<T> {
+    pub center: NDCord<T, 2>,
+    pub radius: T,
+}
+
+impl<T: PrimInt + Signed> ManhattanBall<T> {
+    pub fn new(center: NDCord<T, 2>, radius: T) -> Self {
+        ManhattanBall { center, radius }
+    }
+
+    /// Smallest ball around `center` that reaches `edge`, like a sensor and its closest beacon.
+    pub fn reaching(center: NDCord<T, 2>, edge: NDCord<T, 2>) -> Self {
+        ManhattanBall {
+            center,
+            radius: distance(&center, &edge),
+        }
+    }
+
+    pub fn contains(&self, cord: &NDCord<T, 2>) -> bool {
+        distance(&self.center, cord) <= self.radius
+    }
+
+    /// The ball as a square in the rotated frame.
+    pub fn rotated(&self) -> RotatedRect<T> {
+        let center = to_rotated(self.center);
+        let radius = NDCord([self.radius, self.radius]);
+        RotatedRect {
+            min: center - radius,
+            max: center + radius,
+        }
+    }
+
+    /// Cells in both balls. Intersections aren't always diamonds so the result is a [`RotatedRect`].
+    pub fn intersection(&self, other: &Self) -> Option<RotatedRect<T>> {
+        self.rotated().intersect(&other.rotated())
+    }
+
+    /// Number of cells in the ball.
+    pub fn len(&self) -> u64 {
+        self.rotated().len()
+    }
+
+    /// Only a negative radius has no cells.
+    pub fn is_empty(&self) -> bool {
+        self.radius < T::zero()
+    }
+}
+
+/// Rectangle between `min` and `max` (inclusive) in the rotated frame of [`to_rotated`].
+///
+/// Only rotated cordinates with the same parity on both axes are cells in the original grid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct RotatedRect<T> {
+    pub min: NDCord<T, 2>,
+    pub max: NDCord<T, 2>,
+}
+
+impl<T: PrimInt + Signed> RotatedRect<T> {
+    /// Overlap of two rectangles. [`None`] if they don't overlap, even if the overlap would only have been between cells.
+    pub fn intersect(&self, other: &Self) -> Option<Self> {
+        let min = NDCord([self.min[0].max(other.min[0]), self.min[1].max(other.min[1])]);
+        let max = NDCord([self.max[0].min(other.max[0]), self.max[1].min(other.max[1])]);
+        (min[0] <= max[0] && min[1] <= max[1]).then_some(RotatedRect { min, max })
+    }
+
+    /// Check if a cell from the original grid is in the rectangle.
+    pub fn contains(&self, cord: &NDCord<T, 2>) -> bool {
+        let rotated = to_rotated(*cord);
+        (0..2).all(|axis| self.min[axis] <= rotated[axis] && rotated[axis] <= self.max[axis])
+    }
+
+    /// Number of cells from the original grid in the rectangle.
+    pub fn len(&self) -> u64 {
+        len_in(self.half_open())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Cells from the original grid in the rectangle.
+    pub fn cells(&self) -> impl Iterator<Item = NDCord<T, 2>> {
+        // Beyond any rectangle made from a primitive integer so nothing is clipped.
+        const UNBOUNDED: [i128; 2] = [i128::MIN / 4, i128::MAX / 4];
+        cells_in(self.half_open(), [UNBOUNDED; 2])
+    }
+
+    /// `[start, end)` per axis.
+    fn half_open(&self) -> [[i128; 2]; 2] {
+        [0, 1].map(|axis| [widen(self.min[axis]), widen(self.max[axis]) + 1])
+    }
+}
+
+/// Number of cells of the original grid in a half open rotated rectangle.
+fn len_in(bounds: [[i128; 2]; 2]) -> u64 {
+    let [[u_start, u_end], [v_start, v_end]] = bounds;
+    let count = (0..2)
+        .map(|parity| count_parity(u_start, u_end, parity) * count_parity(v_start, v_end, parity))
+        .sum::<i128>();
+    count.try_into().expect("Count fits in u64")
+}
+
+/// Cells of the original grid in a half open rotated rectangle that are also within the inclusive `[[x_min, x_max], [y_min, y_max]]` box.
+///
+/// Both ranges of rotated cordinates are clipped to the box first so parts of the rectangle outside the box cost nothing.
+fn cells_in<T: PrimInt + Signed>(
+    bounds: [[i128; 2]; 2],
+    clip: [[i128; 2]; 2],
+) -> impl Iterator<Item = NDCord<T, 2>> {
+    let [[u_start, u_end], [v_start, v_end]] = bounds;
+    let [[x_min, x_max], [y_min, y_max]] = clip;
+    // With `x = (u + v) / 2` and `y = (u - v) / 2` each bound on x and y bounds `u` for the extreme values of `v`.
+    let u_min = u_start
+        .max(x_min + y_min)
+        .max(2 * x_min - (v_end - 1))
+        .max(v_start + 2 * y_min);
+    let u_max = (u_end - 1)
+        .min(x_max + y_max)
+        .min(2 * x_max - v_start)
+        .min(v_end - 1 + 2 * y_max);
+    (u_min..=u_max).flat_map(move |u| {
+        let v_min = v_start.max(2 * x_min - u).max(u - 2 * y_max);
+        let v_max = (v_end - 1).min(2 * x_max - u).min(u - 2 * y_min);
+        // First `v` with the same parity as `u`.
+        let first = v_min + (u - v_min).rem_euclid(2);
+        (first..=v_max)
+            .step_by(2)
+            .map(move |v| NDCord([narrow((u + v) / 2), narrow((u - v) / 2)]))
+    })
+}
+
+/// Split the rotated frame into the rectangles between every edge of `rects` and `area`, keeping only those within `area`.
+/// Each piece is either entirely inside or entirely outside each of `rects`.
+fn compress(
+    rects: &[[[i128; 2]; 2]],
+    area: [[i128; 2]; 2],
+) -> impl Iterator<Item = [[i128; 2]; 2]> {
+    let breaks = [0, 1].map(|axis| {
+        let clamp = |x: i128| x.clamp(area[axis][0], area[axis][1]);
+        let mut out: Vec<_> = rects
+            .iter()
+            .flat_map(|rect| rect[axis].map(clamp))
+            .chain(area[axis])
+            .collect();
+        out.sort_unstable();
+        out.dedup();
+        out
+    });
+    NDCartesianProduct::new(breaks.each_ref().map(|x| 0..x.len().saturating_sub(1))).map(
+        move |[u, v]| {
+            [
+                [breaks[0][u], breaks[0][u + 1]],
+                [breaks[1][v], breaks[1][v + 1]],
+            ]
+        },
+    )
+}
+
+fn covered(rects: &[[[i128; 2]; 2]], piece: &[[i128; 2]; 2]) -> bool {
+    rects.iter().any(|rect| {
+        (0..2).all(|axis| rect[axis][0] <= piece[axis][0] && piece[axis][0] < rect[axis][1])
+    })
+}
+
+/// Number of cells in at least one of the balls.
+/// ```
+/// # use advent_lib::{cord::NDCord, manhattan::{union_len, ManhattanBall}};
+/// let balls = [ManhattanBall::new(NDCord([0, 0]), 1), ManhattanBall::new(NDCord([1, 0]), 1)];
+/// assert_eq!(union_len(&balls), 8);
+/// ```
+pub fn union_len<T: PrimInt + Signed>(balls: &[ManhattanBall<T>]) -> u64 {
+    let rects: Vec<_> = balls.iter().map(|x| x.rotated().half_open()).collect();
+    let Some(area) = rects
+        .iter()
+        .copied()
+        .reduce(|a, b| [0, 1].map(|axis| [a[axis][0].min(b[axis][0]), a[axis][1].max(b[axis][1])]))
+    else {
+        return 0;
+    };
+    compress(&rects, area)
+        .filter(|piece| covered(&rects, piece))
+        .map(len_in)
+        .sum()
+}
+
+/// Cells between `min` and `max` (inclusive) in the original grid that aren't in any of the balls.
+///
+/// Only the regions between the balls' edges are searched so this is fast when the box is mostly covered, no matter how large the box is.
+/// ```
+/// # use advent_lib::{cord::NDCord, manhattan::{uncovered_in_box, ManhattanBall}};
+/// // Four diamonds that leave only the center of the box uncovered.
+/// let balls = [[0, 0], [4, 0], [0, 4], [4, 4]].map(|x| ManhattanBall::new(NDCord(x), 3));
+/// let uncovered: Vec<_> = uncovered_in_box(&balls, NDCord([0, 0]), NDCord([4, 4])).collect();
+/// assert_eq!(uncovered, vec![NDCord([2, 2])]);
+/// ```
+pub fn uncovered_in_box<T: PrimInt + Signed>(
+    balls: &[ManhattanBall<T>],
+    min: NDCord<T, 2>,
+    max: NDCord<T, 2>,
+) -> impl Iterator<Item = NDCord<T, 2>> {
+    let rects: Vec<_> = balls.iter().map(|x| x.rotated().half_open()).collect();
+    let [x_min, y_min, x_max, y_max] = [min[0], min[1], max[0], max[1]].map(widen);
+    // Rotated bounding box of the box.
+    let area = [
+        [x_min + y_min, x_max + y_max + 1],
+        [x_min - y_max, x_max - y_min + 1],
+    ];
+    let pieces: Vec<_> = compress(&rects, area)
+        .filter(|piece| !covered(&rects, piece))
+        .collect();
+    pieces
+        .into_iter()
+        .flat_map(move |piece| cells_in(piece, [[x_min, x_max], [y_min, y_max]]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn example() -> Vec<ManhattanBall<i32>> {
+        vec![
+            ManhattanBall::new(NDCord([0, 0]), 3),
+            ManhattanBall::new(NDCord([4, 1]), 2),
+            ManhattanBall::new(NDCord([-2, 5]), 1),
+            ManhattanBall::new(NDCord([1, 2]), 0),
+            ManhattanBall::reaching(NDCord([3, -4]), NDCord([5, -3])),
+        ]
+    }
+
+    /// Every cell that could be in one of the example balls.
+    fn search_space() -> impl Iterator<Item = NDCord<i32, 2>> {
+        NDCord([-10, -10]).interpolate(&NDCord([10, 10]))
+    }
+
+    #[test]
+    fn rotated_test() {
+        for cord in search_space() {
+            assert_eq!(from_rotated(to_rotated(cord)), Some(cord));
+        }
+        assert_eq!(from_rotated(NDCord([1, 0])), None);
+        for ball in example() {
+            for cord in search_space() {
+                assert_eq!(ball.contains(&cord), ball.rotated().contains(&cord));
+            }
+            let cells: HashSet<_> = ball.rotated().cells().collect();
+            assert_eq!(cells.len() as u64, ball.len());
+            assert!(cells.iter().all(|x| ball.contains(x)));
+            assert_eq!(
+                ball.len(),
+                search_space().filter(|x| ball.contains(x)).count() as u64
+            );
+        }
+    }
+
+    #[test]
+    fn intersection_test() {
+        let balls = example();
+        for a in &balls {
+            for b in &balls {
+                let expected: HashSet<_> = search_space()
+                    .filter(|x| a.contains(x) && b.contains(x))
+                    .collect();
+                let intersection = a.intersection(b);
+                let found: HashSet<_> = intersection.iter().flat_map(RotatedRect::cells).collect();
+                assert_eq!(found, expected);
+                assert_eq!(intersection.map_or(0, |x| x.len()), expected.len() as u64);
+            }
+        }
+    }
+
+    #[test]
+    fn union_test() {
+        let balls = example();
+        let expected = search_space()
+            .filter(|x| balls.iter().any(|ball| ball.contains(x)))
+            .count();
+        assert_eq!(union_len(&balls), expected as u64);
+        assert_eq!(union_len::<i32>(&[]), 0);
+    }
+
+    #[test]
+    fn uncovered_test() {
+        let balls = example();
+        let (min, max) = (NDCord([-3, -2]), NDCord([6, 7]));
+        let expected: HashSet<_> = min
+            .interpolate(&max)
+            .filter(|x| !balls.iter().any(|ball| ball.contains(x)))
+            .collect();
+        let found: Vec<_> = uncovered_in_box(&balls, min, max).collect();
+        assert_eq!(found.len(), expected.len());
+        assert_eq!(found.into_iter().collect::<HashSet<_>>(), expected);
+        assert_eq!(
+            uncovered_in_box::<i32>(&[], NDCord([0, 0]), NDCord([1, 1])).count(),
+            4
+        );
+    }
+}
@@ -0,0 +1,63 @@
+/// Offsets of the radius 1 moore neighborhood in `dim` dimensions, `dim` values per neighbor.
+/// Neighbors are in the same order as [`crate::cord::NDCord::moore_neighborhood`] with the center left out.
+pub(crate) fn moore(dim: usize) -> Option<&'static [i8]> {
+    match dim {
+        1 => Some(&MOORE_1),
+        2 => Some(&MOORE_2),
+        3 => Some(&MOORE_3),
+        4 => Some(&MOORE_4),
+        _ => None,
+    }
+}
+
+/// Like [`moore`] but for the radius 1 neumann neighborhood.
+pub(crate) fn neumann(dim: usize) -> Option<&'static [i8]> {
+    match dim {
+        1 => Some(&NEUMANN_1),
+        2 => Some(&NEUMANN_2),
+        3 => Some(&NEUMANN_3),
+        4 => Some(&NEUMANN_4),
+        _ => None,
+    }
+}
+
+static MOORE_1: [i8; 2] = table(1, false);
+static MOORE_2: [i8; 2 * 8] = table(2, false);
+static MOORE_3: [i8; 3 * 26] = table(3, false);
+static MOORE_4: [i8; 4 * 80] = table(4, false);
+static NEUMANN_1: [i8; 2] = table(1, true);
+static NEUMANN_2: [i8; 2 * 4] = table(2, true);
+static NEUMANN_3: [i8; 3 * 6] = table(3, true);
+static NEUMANN_4: [i8; 4 * 8] = table(4, true);
+
+/// Every cell of the `3^dim` cube around the origin except the origin, counting up with the first axis most significant.
+/// Only cells one axis away if `neumann`. `LEN` must be exactly enough to hold them.
+const fn table<const LEN: usize>(dim: usize, neumann: bool) -> [i8; LEN] {
+    let mut out = [0; LEN];
+    let cells = 3usize.pow(dim as u32);
+    let mut len = 0;
+    let mut cell = 0;
+    while cell < cells {
+        let mut offset = [0i8; 8];
+        let mut nonzero = 0;
+        let mut axis = 0;
+        while axis < dim {
+            offset[axis] = (cell / 3usize.pow((dim - 1 - axis) as u32) % 3) as i8 - 1;
+            if offset[axis] != 0 {
+                nonzero += 1;
+            }
+            axis += 1;
+        }
+        if nonzero != 0 && (!neumann || nonzero == 1) {
+            let mut axis = 0;
+            while axis < dim {
+                out[len] = offset[axis];
+                len += 1;
+                axis += 1;
+            }
+        }
+        cell += 1;
+    }
+    assert!(len == LEN, "Table length should match the neighborhood");
+    out
+}
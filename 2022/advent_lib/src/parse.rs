@@ -1,18 +1,769 @@
-use std::fs;
-pub fn read_and_leak(file_path: &str) -> Result<&'static str, std::io::Error> {
-    let input_str = fs::read_to_string(file_path)?;
-    Ok(Box::leak(Box::new(input_str)))
+use std::{
+    fmt, fs,
+    io::{self, BufRead},
+    iter::FusedIterator,
+    ops::Deref,
+    path::Path,
+};
+
+/// Puzzle input held in memory. Parsed output can borrow from it and it's freed when dropped,
+/// so reading an input once per run or per test doesn't keep every copy alive.
+/// ```
+/// # use advent_lib::parse::Input;
+/// use nom::{
+///     character::complete::{alpha1, char, digit1},
+///     combinator::map_res,
+///     error::Error,
+///     multi::separated_list1,
+///     sequence::separated_pair,
+/// };
+/// let input = Input::from("a=1,bc=23");
+/// let pair = separated_pair(alpha1, char('='), map_res(digit1, str::parse::<u32>));
+/// let pairs: Vec<(&str, u32)> = input.parse_with::<_, Error<_>>(separated_list1(char(','), pair))?;
+/// assert_eq!(pairs, [("a", 1), ("bc", 23)]);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Input(String);
+
+impl Input {
+    pub fn read(file_path: impl AsRef<Path>) -> Result<Self, io::Error> {
+        fs::read_to_string(file_path).map(Input)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Run a nom parser on the whole input and return its output. Input the parser leaves over is an error.
+    /// Works with any error type that implements [`NomError`](nom::NomError), like nom's `Error` and `VerboseError`.
+    /// The error owns what it needs so it can be returned with `?` after the input is dropped.
+    pub fn parse_with<'a, O, E>(
+        &'a self,
+        parser: impl ::nom::Parser<&'a str, O, E>,
+    ) -> Result<O, ParseError>
+    where
+        E: nom::NomError<'a> + ::nom::error::ParseError<&'a str>,
+    {
+        parse_all(&self.0, parser).map_err(|e| ParseError::from_nom(&self.0, e))
+    }
+
+    /// The input as a [`nom_locate`] span for parsers that track their own position.
+    /// Errors from parsing it can be turned into a [`ParseError`] with [`ParseError::from_nom`].
+    /// ```
+    /// # use advent_lib::parse::{Input, ParseError, Span};
+    /// use nom::{bytes::complete::tag, character::complete::digit1, error::Error, sequence::preceded};
+    /// let input = Input::from("a\nb2");
+    /// let (_, digits) = preceded(tag::<_, _, Error<Span>>("a\nb"), digit1)(input.span())
+    ///     .map_err(|e| ParseError::from_nom(&input, e))?;
+    /// assert_eq!((digits.location_line(), digits.get_utf8_column()), (2, 2));
+    /// # Ok::<(), ParseError>(())
+    /// ```
+    #[cfg(feature = "nom_locate")]
+    pub fn span(&self) -> Span<'_> {
+        Span::new(&self.0)
+    }
+}
+
+/// Input for [`nom_locate`] parsers, made by [`Input::span`].
+#[cfg(feature = "nom_locate")]
+pub type Span<'a> = nom_locate::LocatedSpan<&'a str>;
+
+/// Run `parser` on all of `text`, failing at whatever it doesn't consume.
+fn parse_all<'a, O, E>(
+    text: &'a str,
+    mut parser: impl ::nom::Parser<&'a str, O, E>,
+) -> Result<O, ::nom::Err<E>>
+where
+    E: ::nom::error::ParseError<&'a str>,
+{
+    ::nom::combinator::all_consuming(|i| parser.parse(i))(text).map(|(_, out)| out)
+}
+
+impl Deref for Input {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl AsRef<str> for Input {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for Input {
+    fn from(value: String) -> Self {
+        Input(value)
+    }
+}
+
+impl From<&str> for Input {
+    fn from(value: &str) -> Self {
+        Input(value.to_owned())
+    }
+}
+
+impl fmt::Display for Input {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Where and why parsing failed, made by both the [`nom`](self::nom) and [`yap`](self::yap) helpers.
+///
+/// Displays as `line 2, column 5: expected digit in "Valve AA"`.
+/// With the `miette` feature it's also a [`miette::Diagnostic`] that labels the failing spot in the snippet.
+/// ```
+/// # use advent_lib::parse::ParseError;
+/// let err = ParseError::new("move 1 from 2 to 3\nmove x from 1 to 2", 24, "digit");
+/// assert_eq!((err.line, err.column), (2, 6));
+/// assert_eq!(err.snippet, "move x from 1 to 2");
+/// assert_eq!(err.to_string(), r#"line 2, column 6: expected digit in "move x from 1 to 2""#);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash, thiserror::Error)]
+#[error("line {line}, column {column}: expected {expected} in {snippet:?}")]
+pub struct ParseError {
+    /// Byte offset into the parsed input.
+    pub offset: usize,
+    /// Line number starting at 1.
+    pub line: usize,
+    /// Character position in the line starting at 1.
+    pub column: usize,
+    pub expected: String,
+    /// Line of input containing the error without its line ending.
+    pub snippet: String,
+}
+
+impl ParseError {
+    /// Error at byte `offset` of `source`. Panics if `offset` isn't on a character boundary of `source`.
+    pub fn new(source: &str, offset: usize, expected: impl Into<String>) -> Self {
+        let line_start = source[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = source[offset..]
+            .find('\n')
+            .map_or(source.len(), |i| offset + i);
+        ParseError {
+            offset,
+            line: source[..line_start].matches('\n').count() + 1,
+            column: source[line_start..offset].chars().count() + 1,
+            expected: expected.into(),
+            snippet: source[line_start..line_end]
+                .trim_end_matches('\r')
+                .to_owned(),
+        }
+    }
+
+    /// Error from a nom parser that was run on `source`.
+    /// An error whose position isn't part of `source`, like one from parsing a copy of it, is put at the end of `source`.
+    pub fn from_nom<'a, E: nom::NomError<'a>>(source: &'a str, err: ::nom::Err<E>) -> Self {
+        match err {
+            ::nom::Err::Error(e) | ::nom::Err::Failure(e) => {
+                let offset = (e.position().as_ptr() as usize)
+                    .checked_sub(source.as_ptr() as usize)
+                    .filter(|&offset| offset <= source.len() && source.is_char_boundary(offset))
+                    .unwrap_or(source.len());
+                Self::new(source, offset, e.expected())
+            }
+            ::nom::Err::Incomplete(_) => Self::new(source, source.len(), "more input"),
+        }
+    }
+
+    /// Byte offset of the error in [`ParseError::snippet`].
+    pub fn snippet_offset(&self) -> usize {
+        self.snippet
+            .char_indices()
+            .nth(self.column - 1)
+            .map_or(self.snippet.len(), |(i, _)| i)
+    }
+}
+
+#[cfg(feature = "miette")]
+impl miette::Diagnostic for ParseError {
+    fn source_code(&self) -> Option<&dyn miette::SourceCode> {
+        Some(self)
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = miette::LabeledSpan> + '_>> {
+        let offset = self.snippet_offset();
+        let len = self.snippet[offset..]
+            .chars()
+            .next()
+            .map_or(0, char::len_utf8);
+        Some(Box::new(std::iter::once(miette::LabeledSpan::new(
+            Some(format!("expected {}", self.expected)),
+            offset,
+            len,
+        ))))
+    }
+}
+
+/// The snippet numbered from the line it's on, so reports show where it is in the whole input.
+#[cfg(feature = "miette")]
+impl miette::SourceCode for ParseError {
+    fn read_span<'a>(
+        &'a self,
+        span: &miette::SourceSpan,
+        context_lines_before: usize,
+        context_lines_after: usize,
+    ) -> Result<Box<dyn miette::SpanContents<'a> + 'a>, miette::MietteError> {
+        let contents = self
+            .snippet
+            .read_span(span, context_lines_before, context_lines_after)?;
+        Ok(Box::new(miette::MietteSpanContents::new(
+            contents.data(),
+            *contents.span(),
+            contents.line() + self.line - 1,
+            contents.column(),
+            contents.line_count(),
+        )))
+    }
+}
+
+/// Splits input into sections of lines separated by one or more blank lines, like the elves' calories or the
+/// crate drawing and the moves. Lines can end in `\n` or `\r\n`, lines with only spaces and tabs count as blank,
+/// and blank lines at the start or end don't make empty sections.
+/// ```
+/// # use advent_lib::parse::sections;
+/// let input = "1000\r\n2000\r\n\r\n3000\r\n\r\n\r\n";
+/// let sums: Vec<u32> = sections(input)
+///     .map(|section| section.text.lines().map(|x| x.parse::<u32>().unwrap()).sum())
+///     .collect();
+/// assert_eq!(sums, [3000, 3000]);
+/// assert_eq!(sections(input).nth(1).unwrap().line, 4);
+/// ```
+pub fn sections(input: &str) -> Sections<'_> {
+    Sections {
+        source: input,
+        offset: 0,
+        line: 1,
+    }
+}
+
+/// Whether `c` can be on a blank line. Everything that splits sections goes through this so they agree.
+fn is_blank(c: char) -> bool {
+    c == ' ' || c == '\t'
+}
+
+/// Whether `line`, with or without its line ending, is blank.
+fn is_blank_line(line: &str) -> bool {
+    let line = line.strip_suffix('\n').unwrap_or(line);
+    let line = line.strip_suffix('\r').unwrap_or(line);
+    line.chars().all(is_blank)
+}
+
+/// Iterator made by [`sections`].
+#[derive(Clone, Debug)]
+pub struct Sections<'a> {
+    source: &'a str,
+    offset: usize,
+    line: usize,
+}
+
+impl<'a> Sections<'a> {
+    /// The line starting at `start` without its line ending, and where the next line starts.
+    fn line_at(&self, start: usize) -> (&'a str, usize) {
+        let rest = &self.source[start..];
+        match rest.find('\n') {
+            Some(i) => (rest[..i].trim_end_matches('\r'), start + i + 1),
+            None => (rest, self.source.len()),
+        }
+    }
+}
+
+impl<'a> Iterator for Sections<'a> {
+    type Item = Section<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Skip blank lines before the section.
+        while self.offset < self.source.len() {
+            let (text, next) = self.line_at(self.offset);
+            if !is_blank_line(text) {
+                break;
+            }
+            (self.offset, self.line) = (next, self.line + 1);
+        }
+        if self.offset >= self.source.len() {
+            return None;
+        }
+        let (start, line) = (self.offset, self.line);
+        let mut end = start;
+        while self.offset < self.source.len() {
+            let (text, next) = self.line_at(self.offset);
+            if is_blank_line(text) {
+                break;
+            }
+            end = self.offset + text.len();
+            (self.offset, self.line) = (next, self.line + 1);
+        }
+        Some(Section {
+            text: &self.source[start..end],
+            line,
+            offset: start,
+            source: self.source,
+        })
+    }
+}
+
+impl FusedIterator for Sections<'_> {}
+
+/// Lines of input between blank lines made by [`sections`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Section<'a> {
+    /// Lines of the section without the last line's ending.
+    pub text: &'a str,
+    /// Line number of the first line starting at 1.
+    pub line: usize,
+    /// Byte offset of the first line in the whole input.
+    pub offset: usize,
+    source: &'a str,
+}
+
+impl<'a> Section<'a> {
+    /// Run a nom parser on the whole section like [`Input::parse_with`]. The error's line and column are in the whole input.
+    pub fn parse_with<O, E>(
+        &self,
+        parser: impl ::nom::Parser<&'a str, O, E>,
+    ) -> Result<O, ParseError>
+    where
+        E: nom::NomError<'a> + ::nom::error::ParseError<&'a str>,
+    {
+        parse_all(self.text, parser).map_err(|e| ParseError::from_nom(self.source, e))
+    }
+
+    /// Move an error from parsing [`Section::text`] by itself to where it is in the whole input.
+    /// ```
+    /// # use advent_lib::parse::{sections, yap::all_consuming};
+    /// use yap::{IntoTokens, Tokens};
+    /// let second = sections("ab\n\nab\nabc").nth(1).unwrap();
+    /// let err = all_consuming(&mut second.text.into_tokens(), |t| {
+    ///     t.skip_tokens_while(|c| *c != 'c');
+    ///     t.skip_tokens_while(|c| *c == 'z');
+    /// })
+    /// .unwrap_err();
+    /// assert_eq!((err.line, err.column), (2, 3));
+    /// let err = second.locate(err);
+    /// assert_eq!((err.line, err.column, err.snippet.as_str()), (4, 3, "abc"));
+    /// ```
+    pub fn locate(&self, err: ParseError) -> ParseError {
+        ParseError::new(self.source, self.offset + err.offset, err.expected)
+    }
+}
+
+/// Lines of `reader` read one at a time without their line endings. Only the current line is held in memory.
+/// ```
+/// # use advent_lib::parse::read_lines;
+/// let lines: Vec<_> = read_lines("a\r\nb\n".as_bytes()).collect::<Result<_, _>>()?;
+/// assert_eq!((lines[1].text.as_str(), lines[1].line, lines[1].offset), ("b", 2, 3));
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn read_lines<R: BufRead>(reader: R) -> ReadLines<R> {
+    ReadLines(RawLines::new(reader))
+}
+
+/// Like [`sections`] but reading from `reader` one section at a time, so only the current section is held in memory.
+/// A section with a line that can't be read is an error, and the sections after it are still split the same way.
+/// ```
+/// # use advent_lib::parse::read_sections;
+/// let input = "1000\n2000\n\n3000\n";
+/// let totals = read_sections(input.as_bytes())
+///     .map(|section| Ok(section?.text.lines().map(str::parse::<u32>).sum::<Result<u32, _>>()?))
+///     .collect::<Result<Vec<_>, Box<dyn std::error::Error>>>()?;
+/// assert_eq!(totals, [3000, 3000]);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn read_sections<R: BufRead>(reader: R) -> ReadSections<R> {
+    ReadSections {
+        lines: RawLines::new(reader),
+        skipping: false,
+    }
+}
+
+/// Reads lines with their line endings and keeps track of where they start.
+#[derive(Debug)]
+struct RawLines<R> {
+    reader: R,
+    line: usize,
+    offset: usize,
+    /// Set at the end of input so a reader that has more later isn't read again.
+    done: bool,
+}
+
+impl<R: BufRead> RawLines<R> {
+    fn new(reader: R) -> Self {
+        RawLines {
+            reader,
+            line: 1,
+            offset: 0,
+            done: false,
+        }
+    }
+
+    /// The next line with its line ending and where it starts, or [`None`] at the end of input.
+    /// A line that isn't UTF-8 is an error but is still counted, so the lines after it are numbered right.
+    fn next_raw(&mut self) -> io::Result<Option<Chunk>> {
+        if self.done {
+            return Ok(None);
+        }
+        let mut bytes = Vec::new();
+        let read = self.reader.read_until(b'\n', &mut bytes);
+        // Whatever was read is consumed even if reading failed partway.
+        let (line, offset) = (self.line, self.offset);
+        self.offset += bytes.len();
+        if bytes.ends_with(b"\n") {
+            self.line += 1;
+        }
+        if read? == 0 {
+            self.done = true;
+            return Ok(None);
+        }
+        let text = String::from_utf8(bytes).map_err(|e| {
+            let message = format!("line {line} isn't valid UTF-8: {e}");
+            io::Error::new(io::ErrorKind::InvalidData, message)
+        })?;
+        Ok(Some(Chunk { text, line, offset }))
+    }
+}
+
+/// Removes one `\n` or `\r\n` from the end of `text`.
+fn trim_line_ending(text: &mut String) {
+    if text.ends_with('\n') {
+        text.pop();
+        if text.ends_with('\r') {
+            text.pop();
+        }
+    }
+}
+
+/// Iterator made by [`read_lines`].
+#[derive(Debug)]
+pub struct ReadLines<R>(RawLines<R>);
+
+impl<R: BufRead> Iterator for ReadLines<R> {
+    type Item = io::Result<Chunk>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next_raw().transpose().map(|line| {
+            let mut line = line?;
+            trim_line_ending(&mut line.text);
+            Ok(line)
+        })
+    }
+}
+
+impl<R: BufRead> FusedIterator for ReadLines<R> {}
+
+/// Iterator made by [`read_sections`].
+#[derive(Debug)]
+pub struct ReadSections<R> {
+    lines: RawLines<R>,
+    /// Set after an error so the rest of its section isn't returned as a section of its own.
+    skipping: bool,
+}
+
+impl<R: BufRead> Iterator for ReadSections<R> {
+    type Item = io::Result<Chunk>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let is_blank = |line: &Chunk| is_blank_line(&line.text);
+        while self.skipping {
+            match self.lines.next_raw() {
+                Ok(Some(line)) if !is_blank(&line) => continue,
+                Ok(_) => self.skipping = false,
+                Err(e) => return Some(Err(e)),
+            }
+        }
+        // Skip blank lines before the section.
+        let mut section = loop {
+            match self.lines.next_raw() {
+                Ok(Some(line)) if is_blank(&line) => continue,
+                Ok(Some(line)) => break line,
+                Ok(None) => return None,
+                Err(e) => {
+                    self.skipping = true;
+                    return Some(Err(e));
+                }
+            }
+        };
+        loop {
+            match self.lines.next_raw() {
+                Ok(Some(line)) if !is_blank(&line) => section.text.push_str(&line.text),
+                Ok(_) => break,
+                Err(e) => {
+                    self.skipping = true;
+                    return Some(Err(e));
+                }
+            }
+        }
+        trim_line_ending(&mut section.text);
+        Some(Ok(section))
+    }
+}
+
+impl<R: BufRead> FusedIterator for ReadSections<R> {}
+
+/// A line or section read by [`read_lines`] or [`read_sections`] along with where it starts in the stream.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Chunk {
+    /// Text without the last line's ending.
+    pub text: String,
+    /// Line number of the first line starting at 1.
+    pub line: usize,
+    /// Byte offset of the first line in the stream.
+    pub offset: usize,
+}
+
+impl Chunk {
+    /// Run a nom parser on the whole chunk like [`Input::parse_with`]. The error's line and offset are in the whole stream.
+    pub fn parse_with<'a, O, E>(
+        &'a self,
+        parser: impl ::nom::Parser<&'a str, O, E>,
+    ) -> Result<O, ParseError>
+    where
+        E: nom::NomError<'a> + ::nom::error::ParseError<&'a str>,
+    {
+        parse_all(&self.text, parser).map_err(|e| self.locate(ParseError::from_nom(&self.text, e)))
+    }
+
+    /// Move an error from parsing [`Chunk::text`] by itself to where it is in the stream.
+    /// ```
+    /// # use advent_lib::parse::{read_lines, Chunk};
+    /// use advent_lib::scan;
+    /// let line: Chunk = read_lines("1 2\n3 x".as_bytes()).nth(1).unwrap()?;
+    /// let err = scan!(line.text, "{u8} {u8}").map_err(|e| line.locate(e)).unwrap_err();
+    /// assert_eq!((err.line, err.column, err.offset), (2, 3, 6));
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn locate(&self, err: ParseError) -> ParseError {
+        ParseError {
+            offset: self.offset + err.offset,
+            line: self.line + err.line - 1,
+            ..err
+        }
+    }
+}
+
+/// Pieces used by the code [`scan!`](crate::scan) expands to. Each step moves past what it matched.
+pub mod scan {
+    use super::ParseError;
+    use std::{any, str::FromStr};
+
+    /// Where a capture stops.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum End<'a> {
+        /// Before the next occurrence of this text.
+        Text(&'a str),
+        /// Before the next whitespace, or the next occurrence of the text if that comes first.
+        Whitespace(Option<&'a str>),
+        /// At the end of the input.
+        Input,
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct Scanner<'a> {
+        source: &'a str,
+        offset: usize,
+    }
+
+    impl<'a> Scanner<'a> {
+        pub fn new(source: &'a str) -> Self {
+            Scanner { source, offset: 0 }
+        }
+
+        fn rest(&self) -> &'a str {
+            &self.source[self.offset..]
+        }
+
+        fn error(&self, offset: usize, expected: impl Into<String>) -> ParseError {
+            ParseError::new(self.source, offset, expected)
+        }
+
+        pub fn literal(&mut self, text: &str) -> Result<(), ParseError> {
+            if self.rest().starts_with(text) {
+                self.offset += text.len();
+                Ok(())
+            } else {
+                Err(self.error(self.offset, format!("{text:?}")))
+            }
+        }
+
+        /// Skip whitespace, failing unless there's at least one character of it. The start and end of the
+        /// input count, as does whitespace a list already ran over.
+        pub fn whitespace(&mut self) -> Result<(), ParseError> {
+            let rest = self.rest();
+            let len = rest.len() - rest.trim_start().len();
+            let before = self.source[..self.offset].chars().next_back();
+            if len == 0 && !rest.is_empty() && before.is_some_and(|c| !c.is_whitespace()) {
+                return Err(self.error(self.offset, "whitespace"));
+            }
+            self.offset += len;
+            Ok(())
+        }
+
+        /// Skip any amount of whitespace, including none.
+        pub fn optional_whitespace(&mut self) {
+            let rest = self.rest();
+            self.offset += rest.len() - rest.trim_start().len();
+        }
+
+        /// Take the text up to `end`, failing if there's none. Text ending the capture is only looked for
+        /// after `skip` bytes.
+        fn take(&mut self, end: End, skip: usize) -> Result<&'a str, ParseError> {
+            let rest = self.rest();
+            let find = |haystack: &str, text| Some(haystack.get(skip..)?.find(text)? + skip);
+            let len = match end {
+                End::Text(text) => find(rest, text)
+                    .ok_or_else(|| self.error(self.source.len(), format!("{text:?}")))?,
+                End::Whitespace(text) => {
+                    let whitespace = rest.find(char::is_whitespace).unwrap_or(rest.len());
+                    text.and_then(|text| find(&rest[..whitespace], text))
+                        .unwrap_or(whitespace)
+                }
+                End::Input => rest.len(),
+            };
+            self.offset += len;
+            Ok(&rest[..len])
+        }
+
+        /// Parse the text up to `end`, failing if there's none.
+        pub fn capture<T: FromStr>(&mut self, end: End) -> Result<T, ParseError> {
+            let start = self.offset;
+            // A capture is never empty, so `-` in `{i32}-{i32}` can't end it before it starts.
+            let first = self.rest().chars().next().map_or(0, char::len_utf8);
+            let text = self.take(end, first)?;
+            (!text.is_empty())
+                .then(|| text.parse().ok())
+                .flatten()
+                .ok_or_else(|| self.error(start, any::type_name::<T>()))
+        }
+
+        /// Parse the text up to `end` as a list split by `separator`, or by whitespace if `separator` is empty.
+        /// Whitespace around each item is ignored and an empty list is allowed.
+        pub fn list<T: FromStr>(
+            &mut self,
+            separator: &str,
+            end: End,
+        ) -> Result<Vec<T>, ParseError> {
+            let start = self.offset;
+            let text = self.take(end, 0)?;
+            let items: Box<dyn Iterator<Item = &str>> = if separator.is_empty() {
+                Box::new(text.split_whitespace())
+            } else if text.trim().is_empty() {
+                Box::new(std::iter::empty())
+            } else {
+                Box::new(text.split(separator).map(str::trim))
+            };
+            items
+                .map(|item| {
+                    item.parse().map_err(|_| {
+                        // Items are subslices of the source so their position can be recovered.
+                        let offset = item.as_ptr() as usize - self.source.as_ptr() as usize;
+                        self.error(offset.max(start), any::type_name::<T>())
+                    })
+                })
+                .collect()
+        }
+
+        /// Check that only whitespace is left.
+        pub fn finish(&mut self) -> Result<(), ParseError> {
+            let rest = self.rest().trim_start();
+            if rest.is_empty() {
+                Ok(())
+            } else {
+                Err(self.error(self.source.len() - rest.len(), "end of input"))
+            }
+        }
+    }
 }
 
 pub mod nom {
     use nom::{
-        character::complete::one_of,
-        combinator::{opt, recognize},
+        bytes::complete::take_while,
+        character::complete::{line_ending, one_of},
+        combinator::{eof, not, opt, recognize, value},
         error::ParseError,
-        sequence::tuple,
-        AsChar, FindToken, IResult, InputIter, Parser,
+        multi::{many0_count, many1_count, separated_list1},
+        sequence::{pair, terminated, tuple},
+        AsChar, Compare, FindToken, IResult, InputIter, InputLength, InputTakeAtPosition, Parser,
+        Slice,
+    };
+    use std::{
+        ops::{Range, RangeFrom, RangeTo},
+        str::FromStr,
     };
-    use std::str::FromStr;
+
+    /// nom error types that can be turned into a [`ParseError`](super::ParseError) with [`ParseError::from_nom`](super::ParseError::from_nom).
+    pub trait NomError<'a> {
+        /// Input left when the error happened.
+        fn position(&self) -> &'a str;
+        /// What the parser was looking for.
+        fn expected(&self) -> String;
+    }
+
+    impl<'a> NomError<'a> for nom::error::Error<&'a str> {
+        fn position(&self) -> &'a str {
+            self.input
+        }
+
+        fn expected(&self) -> String {
+            self.code.description().to_lowercase()
+        }
+    }
+
+    #[cfg(feature = "nom_locate")]
+    impl<'a> NomError<'a> for nom::error::Error<super::Span<'a>> {
+        fn position(&self) -> &'a str {
+            self.input.fragment()
+        }
+
+        fn expected(&self) -> String {
+            self.code.description().to_lowercase()
+        }
+    }
+
+    /// Uses the innermost error and the innermost [`context`](nom::error::context) around it.
+    impl<'a> NomError<'a> for nom::error::VerboseError<&'a str> {
+        fn position(&self) -> &'a str {
+            self.errors.first().map_or("", |(input, _)| input)
+        }
+
+        fn expected(&self) -> String {
+            verbose_expected(&self.errors)
+        }
+    }
+
+    /// Like the [`VerboseError`](nom::error::VerboseError) over `&str`.
+    #[cfg(feature = "nom_locate")]
+    impl<'a> NomError<'a> for nom::error::VerboseError<super::Span<'a>> {
+        fn position(&self) -> &'a str {
+            self.errors
+                .first()
+                .map_or("", |(input, _)| input.fragment())
+        }
+
+        fn expected(&self) -> String {
+            verbose_expected(&self.errors)
+        }
+    }
+
+    fn verbose_expected<I>(errors: &[(I, nom::error::VerboseErrorKind)]) -> String {
+        use nom::error::VerboseErrorKind;
+        let kind = errors.first().map(|(_, kind)| match kind {
+            VerboseErrorKind::Char(c) => format!("{c:?}"),
+            VerboseErrorKind::Context(context) => context.to_string(),
+            VerboseErrorKind::Nom(kind) => kind.description().to_lowercase(),
+        });
+        let context = errors.iter().skip(1).find_map(|(_, kind)| match kind {
+            VerboseErrorKind::Context(context) => Some(context),
+            _ => None,
+        });
+        match (kind, context) {
+            (Some(kind), Some(context)) => format!("{kind} in {context}"),
+            (Some(kind), None) => kind,
+            (None, _) => "valid input".to_owned(),
+        }
+    }
 
     /// Takes a combinator and converts the output to a &str before parsing with `str.parse()`. Returns `nom::error::ErrorKind::Fail` on fail.
     pub fn parse_from<'a, F, I, O, E>(mut f: F) -> impl FnMut(I) -> IResult<I, O, E>
@@ -24,14 +775,13 @@ pub mod nom {
     {
         move |input: I| {
             let (input, out) = f.parse(input)?;
-            let Ok(out) = (out.into()).parse::<O>()
-        else {
-            // If there is FromStr error the output type can't be parsed from the original combinator's output.
-            return Err(nom::Err::Failure(E::from_error_kind(
-                input,
-                nom::error::ErrorKind::Fail,
-            )));
-        };
+            let Ok(out) = (out.into()).parse::<O>() else {
+                // If there is FromStr error the output type can't be parsed from the original combinator's output.
+                return Err(nom::Err::Failure(E::from_error_kind(
+                    input,
+                    nom::error::ErrorKind::Fail,
+                )));
+            };
             Ok((input, out))
         }
     }
@@ -54,6 +804,71 @@ pub mod nom {
         recognize(tuple((opt(one_of("-+")), f)))
     }
 
+    /// Parses the line ending after a section's last line and the blank lines after it, the same breaks
+    /// [`sections`](super::sections) splits on.
+    pub fn section_break<I, E>(input: I) -> IResult<I, (), E>
+    where
+        I: Clone
+            + InputLength
+            + InputIter
+            + InputTakeAtPosition
+            + Slice<Range<usize>>
+            + Slice<RangeFrom<usize>>
+            + Slice<RangeTo<usize>>
+            + Compare<&'static str>,
+        <I as InputTakeAtPosition>::Item: AsChar + Clone,
+        E: ParseError<I>,
+    {
+        value((), pair(line_ending, many1_count(blank_line)))(input)
+    }
+
+    /// Parses a line with only spaces or tabs and its line ending.
+    fn blank_line<I, E>(input: I) -> IResult<I, (), E>
+    where
+        I: Clone
+            + InputLength
+            + InputIter
+            + InputTakeAtPosition
+            + Slice<Range<usize>>
+            + Slice<RangeFrom<usize>>
+            + Slice<RangeTo<usize>>
+            + Compare<&'static str>,
+        <I as InputTakeAtPosition>::Item: AsChar + Clone,
+        E: ParseError<I>,
+    {
+        let blank = |c: <I as InputTakeAtPosition>::Item| super::is_blank(c.as_char());
+        value((), pair(take_while(blank), line_ending))(input)
+    }
+
+    /// Parses one or more sections with `f` separated by [`section_break`], then the line ending and blank lines at the end.
+    /// `f` shouldn't parse the line ending of a section's last line.
+    /// ```
+    /// # use advent_lib::parse::nom::sections;
+    /// use nom::{character::complete::{digit1, line_ending}, multi::separated_list1};
+    /// let input = "1\r\n2\r\n\r\n3\r\n\r\n";
+    /// let out = sections(separated_list1(line_ending, digit1::<_, ()>))(input);
+    /// assert_eq!(out, Ok(("", vec![vec!["1", "2"], vec!["3"]])));
+    /// ```
+    pub fn sections<I, O, E, F>(f: F) -> impl FnMut(I) -> IResult<I, Vec<O>, E>
+    where
+        I: Clone
+            + InputLength
+            + InputIter
+            + InputTakeAtPosition
+            + Slice<Range<usize>>
+            + Slice<RangeFrom<usize>>
+            + Slice<RangeTo<usize>>
+            + Compare<&'static str>,
+        <I as InputTakeAtPosition>::Item: AsChar + Clone,
+        E: ParseError<I>,
+        F: Parser<I, O, E>,
+    {
+        terminated(
+            separated_list1(terminated(section_break, not(eof)), f),
+            pair(opt(line_ending), many0_count(blank_line)),
+        )
+    }
+
     mod test {
         // For some reason the imports are indicated as unused but they aren't.
         #[allow(unused_imports)]
@@ -89,10 +904,36 @@ pub mod nom {
 }
 
 pub mod yap {
-    use std::{borrow::Borrow, char, str::FromStr};
+    use num_traits::PrimInt;
+    use std::{any, borrow::Borrow, char, fmt::Display, str::FromStr};
     use yap::Tokens;
 
+    /// Why a yap parser failed: the [`Tokens::offset`] it failed at and what it wanted there.
+    /// [`try_all_consuming`] turns it into a [`ParseError`](super::ParseError) with a line and column.
+    #[derive(Clone, Debug, PartialEq, Eq, Hash, thiserror::Error)]
+    #[error("expected {expected} at offset {offset}")]
+    pub struct TokenError {
+        pub offset: usize,
+        pub expected: String,
+    }
+
+    impl TokenError {
+        /// Error at the current position of `tokens`.
+        pub fn at(tokens: &impl Tokens, expected: impl Into<String>) -> Self {
+            TokenError {
+                offset: tokens.offset(),
+                expected: expected.into(),
+            }
+        }
+
+        /// The error in `source`, the text the tokens were made from with `into_tokens()`.
+        pub fn locate(self, source: &str) -> super::ParseError {
+            super::ParseError::new(source, self.offset, self.expected)
+        }
+    }
+
     /// Attempt to parse a tag from an input.
+    #[deprecated(note = "use `literal`, which says where it failed")]
     pub fn tag<Input, T>(input: &mut Input, tag: T) -> Option<T>
     where
         Input: Tokens,
@@ -100,25 +941,29 @@ pub mod yap {
         T: IntoIterator + Clone,
         <T as IntoIterator>::Item: Borrow<<Input as Tokens>::Item>,
     {
-        input.tokens(tag.clone()).then(|| tag)
+        input.tokens(tag.clone()).then_some(tag)
     }
 
-    /// Use [`str::parse`] to parse some amount of input after taking some input matching a predicate.
-    pub fn parse_from1<I, O, F>(
-        input: &mut I,
-        take_while: F,
-    ) -> Option<Result<O, <O as FromStr>::Err>>
+    /// Use [`str::parse`] to parse the input matching a predicate, failing if there's none or it doesn't parse.
+    /// Consumes nothing on fail.
+    pub fn parse_from1<I, O, F>(input: &mut I, mut take_while: F) -> Result<O, TokenError>
     where
         I: Tokens<Item = char>,
         O: FromStr,
         F: FnMut(&I::Item) -> bool,
     {
-        let to_parse = input.tokens_while(take_while).collect::<String>();
-        (!to_parse.is_empty()).then(|| to_parse.parse::<O>())
+        input.optional_err(|t| {
+            let err = TokenError::at(t, any::type_name::<O>());
+            let to_parse = t.tokens_while(&mut take_while).collect::<String>();
+            match to_parse.is_empty() {
+                true => Err(err),
+                false => to_parse.parse().map_err(|_| err),
+            }
+        })
     }
 
     /// Parses at least 1 digit.
-    pub fn digit1<I, O>(input: &mut I) -> Option<Result<O, <O as FromStr>::Err>>
+    pub fn digit1<I, O>(input: &mut I) -> Result<O, TokenError>
     where
         I: Tokens<Item = char>,
         O: FromStr,
@@ -128,18 +973,17 @@ pub mod yap {
     }
 
     /// Parses at least 1 digit with an optional sign (`+`/`-`) in front.
-    pub fn signed_digit1<I, O>(input: &mut I) -> Option<Result<O, <O as FromStr>::Err>>
+    pub fn signed_digit1<I, O>(input: &mut I) -> Result<O, TokenError>
     where
         I: Tokens<Item = char>,
-        O: FromStr + std::fmt::Debug,
-        <O as FromStr>::Err: std::fmt::Debug,
+        O: FromStr,
     {
         let take_while = |&t: &char| t.is_numeric() || t == '+' || t == '-';
         parse_from1(input, take_while)
     }
 
     /// Parses at least 1 alphabetical character.
-    pub fn alpha1<I, O>(input: &mut I) -> Option<Result<O, <O as FromStr>::Err>>
+    pub fn alpha1<I, O>(input: &mut I) -> Result<O, TokenError>
     where
         I: Tokens<Item = char>,
         O: FromStr,
@@ -148,12 +992,372 @@ pub mod yap {
         parse_from1(input, take_while)
     }
 
-    /// Parses a line ending of either `\n` (like on linux)  or `\r\n` (like on windows)
-    pub fn line_ending(tokens: &mut impl Tokens<Item = char>) -> Option<&str> {
+    /// Parses a line ending of either `\n` (like on linux)  or `\r\n` (like on windows). Consumes nothing on fail.
+    pub fn line_ending(tokens: &mut impl Tokens<Item = char>) -> Result<&'static str, TokenError> {
         yap::one_of!(tokens;
             tokens.optional(|t| t.token('\n').then_some("\n")),
             tokens.optional(|t| t.tokens("\r\n".chars()).then_some("\r\n")),
         )
+        .ok_or_else(|| TokenError::at(tokens, "line ending"))
+    }
+
+    /// Parses a line with only spaces or tabs and its line ending. Consumes nothing on fail.
+    fn blank_line(tokens: &mut impl Tokens<Item = char>) -> bool {
+        tokens
+            .optional(|t| {
+                t.skip_tokens_while(|&c| super::is_blank(c));
+                line_ending(t).ok()
+            })
+            .is_some()
+    }
+
+    /// Parses the line ending after a section's last line and the blank lines after it, the same breaks
+    /// [`sections`](super::sections) splits on. Consumes nothing on fail.
+    pub fn section_break(tokens: &mut impl Tokens<Item = char>) -> Result<(), TokenError> {
+        tokens.optional_err(|t| {
+            line_ending(t)?;
+            match t.skip_many(blank_line) {
+                0 => Err(TokenError::at(t, "blank line")),
+                _ => Ok(()),
+            }
+        })
+    }
+
+    /// Parses sections with `parser` separated by [`section_break`], then the line ending and blank lines at the end.
+    /// `parser` shouldn't parse the line ending of a section's last line.
+    ///
+    /// Like [`sep_by`], a section that fails after its first token is an error rather than the end of the sections.
+    /// Consumes nothing on fail.
+    /// ```
+    /// # use advent_lib::parse::yap::{int, line_ending, literal, sections, sep_by1, try_all_consuming};
+    /// use yap::IntoTokens;
+    /// let out = try_all_consuming(&mut "1\n2\n\n \n3\n\n".into_tokens(), |t| {
+    ///     sections(t, |t| {
+    ///         let nums = sep_by1(t, int::<u32>, |t| line_ending(t).is_ok())?;
+    ///         Ok(nums.iter().sum::<u32>())
+    ///     })
+    /// });
+    /// assert_eq!(out, Ok(vec![3, 3]));
+    /// let item = |t: &mut _| int::<u32>(t).and_then(|x| literal(t, ";").map(|_| x));
+    /// let err = sections(&mut "1;\n\n2x".into_tokens(), item).unwrap_err();
+    /// assert_eq!((err.offset, err.expected.as_str()), (5, "\";\""));
+    /// ```
+    pub fn sections<T, O, F>(tokens: &mut T, mut parser: F) -> Result<Vec<O>, TokenError>
+    where
+        T: Tokens<Item = char>,
+        F: FnMut(&mut T) -> Result<O, TokenError>,
+    {
+        // A break at the end is left for the trailing blank lines so `parser` isn't tried on nothing.
+        let mut separator = |t: &mut T| {
+            t.optional(|t| (section_break(t).is_ok() && !eof(t)).then_some(()))
+                .is_some()
+        };
+        tokens.optional_err(|t| {
+            let out = sep_by(t, &mut parser, &mut separator)?;
+            line_ending(t).ok();
+            t.skip_many(blank_line);
+            Ok(out)
+        })
+    }
+
+    /// Parses exactly `text`. Consumes nothing on fail.
+    pub fn literal(tokens: &mut impl Tokens<Item = char>, text: &str) -> Result<(), TokenError> {
+        let start = tokens.location();
+        if tokens.tokens(text.chars()) {
+            Ok(())
+        } else {
+            tokens.set_location(start);
+            Err(TokenError::at(tokens, format!("{text:?}")))
+        }
+    }
+
+    /// Skips spaces and tabs and returns how many there were.
+    pub fn space0(tokens: &mut impl Tokens<Item = char>) -> usize {
+        tokens.skip_tokens_while(|&c| c == ' ' || c == '\t')
+    }
+
+    /// Skips at least one space or tab.
+    pub fn space1(tokens: &mut impl Tokens<Item = char>) -> Result<usize, TokenError> {
+        match space0(tokens) {
+            0 => Err(TokenError::at(tokens, "space")),
+            n => Ok(n),
+        }
+    }
+
+    /// Skips whitespace including line endings and returns how many characters there were.
+    pub fn multispace0(tokens: &mut impl Tokens<Item = char>) -> usize {
+        tokens.skip_tokens_while(|c| c.is_whitespace())
+    }
+
+    /// Skips at least one whitespace character including line endings.
+    pub fn multispace1(tokens: &mut impl Tokens<Item = char>) -> Result<usize, TokenError> {
+        match multispace0(tokens) {
+            0 => Err(TokenError::at(tokens, "whitespace")),
+            n => Ok(n),
+        }
+    }
+
+    fn is_signed<N: PrimInt>() -> bool {
+        N::min_value() < N::zero()
+    }
+
+    /// Parses an integer with a `-` or `+` in front if `N` is signed. Consumes nothing on fail.
+    ///
+    /// A number that doesn't fit in `N` is an error at the first digit that overflows.
+    /// ```
+    /// # use advent_lib::parse::yap::int;
+    /// use yap::IntoTokens;
+    /// assert_eq!(int::<i8>(&mut "-128,".into_tokens()), Ok(-128));
+    /// let err = int::<u8>(&mut "1000".into_tokens()).unwrap_err();
+    /// assert_eq!((err.offset, err.expected.as_str()), (3, "u8 from 0 to 255"));
+    /// assert!(int::<u8>(&mut "-1".into_tokens()).is_err());
+    /// ```
+    pub fn int<N>(tokens: &mut impl Tokens<Item = char>) -> Result<N, TokenError>
+    where
+        N: PrimInt + Display,
+    {
+        tokens.optional_err(|t| {
+            let negative = is_signed::<N>()
+                && match t.peek() {
+                    Some('-') => t.next().is_some(),
+                    Some('+') => t.next().is_none(),
+                    _ => false,
+                };
+            let ten = N::from(10).expect("every integer type holds 10");
+            let mut out = None;
+            while let Some(digit) = t.peek().and_then(|c| c.to_digit(10)) {
+                let overflow = TokenError::at(
+                    t,
+                    format!(
+                        "{} from {} to {}",
+                        any::type_name::<N>(),
+                        N::min_value(),
+                        N::max_value()
+                    ),
+                );
+                t.next();
+                let digit = N::from(digit).expect("every integer type holds a digit");
+                let shifted = out.unwrap_or_else(N::zero).checked_mul(&ten);
+                out = Some(
+                    match negative {
+                        true => shifted.and_then(|x| x.checked_sub(&digit)),
+                        false => shifted.and_then(|x| x.checked_add(&digit)),
+                    }
+                    .ok_or(overflow)?,
+                );
+            }
+            out.ok_or_else(|| TokenError::at(t, any::type_name::<N>()))
+        })
+    }
+
+    /// Checks if an integer that [`int`] would parse comes next without consuming anything.
+    fn at_int<N: PrimInt>(tokens: &mut impl Tokens<Item = char>) -> bool {
+        let start = tokens.location();
+        if is_signed::<N>() && matches!(tokens.peek(), Some('-' | '+')) {
+            tokens.next();
+        }
+        let found = tokens.next().is_some_and(|c| c.is_ascii_digit());
+        tokens.set_location(start);
+        found
+    }
+
+    /// Parses every integer in the rest of the line, skipping anything between them. The line ending is left unparsed.
+    /// ```
+    /// # use advent_lib::parse::yap::{ints_in_line, line_ending};
+    /// use yap::IntoTokens;
+    /// let tokens = &mut "Sensor at x=2, y=-18: beacon at x=-2, y=15\nx=1".into_tokens();
+    /// assert_eq!(ints_in_line::<i32>(tokens), Ok(vec![2, -18, -2, 15]));
+    /// assert!(line_ending(tokens).is_ok());
+    /// // Only a signed type takes the `-`.
+    /// assert_eq!(ints_in_line::<u32>(&mut "1-2".into_tokens()), Ok(vec![1, 2]));
+    /// ```
+    pub fn ints_in_line<N>(tokens: &mut impl Tokens<Item = char>) -> Result<Vec<N>, TokenError>
+    where
+        N: PrimInt + Display,
+    {
+        let mut out = Vec::new();
+        loop {
+            let start = tokens.location();
+            if line_ending(tokens).is_ok() {
+                tokens.set_location(start);
+                return Ok(out);
+            }
+            if at_int::<N>(tokens) {
+                out.push(int(tokens)?);
+            } else if tokens.next().is_none() {
+                return Ok(out);
+            }
+        }
+    }
+
+    /// Runs `parser` for an item of a list. [`None`] if it failed at the item's first token, which ends the list.
+    fn list_item<T, O, F>(tokens: &mut T, parser: &mut F) -> Result<Option<O>, TokenError>
+    where
+        T: Tokens,
+        F: FnMut(&mut T) -> Result<O, TokenError>,
+    {
+        let start = tokens.offset();
+        match tokens.optional_err(|t| parser(t)) {
+            // Stop instead of looping forever on a parser that succeeds with nothing.
+            Ok(_) if tokens.offset() == start => Ok(None),
+            Ok(item) => Ok(Some(item)),
+            Err(e) if e.offset == start => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Parses items with `parser` until one can't start. Consumes nothing on fail.
+    ///
+    /// An item that fails after its first token is an error instead of the end of the list, so a typo in the
+    /// middle of the input isn't mistaken for where the list stops. The same goes for [`many1`], [`sep_by`] and [`sep_by1`].
+    /// ```
+    /// # use advent_lib::parse::yap::{int, literal, many};
+    /// use yap::IntoTokens;
+    /// let mut item = |t: &mut _| {
+    ///     literal(t, "#")?;
+    ///     int::<u8>(t)
+    /// };
+    /// assert_eq!(many(&mut "#1#2x".into_tokens(), &mut item), Ok(vec![1, 2]));
+    /// assert_eq!(many(&mut "#1#x".into_tokens(), &mut item).unwrap_err().offset, 3);
+    /// ```
+    pub fn many<T, O, F>(tokens: &mut T, mut parser: F) -> Result<Vec<O>, TokenError>
+    where
+        T: Tokens,
+        F: FnMut(&mut T) -> Result<O, TokenError>,
+    {
+        tokens.optional_err(|t| {
+            let mut out = Vec::new();
+            while let Some(item) = list_item(t, &mut parser)? {
+                out.push(item);
+            }
+            Ok(out)
+        })
+    }
+
+    /// Like [`many`] but fails with the first item's error if there isn't one.
+    pub fn many1<T, O, F>(tokens: &mut T, mut parser: F) -> Result<Vec<O>, TokenError>
+    where
+        T: Tokens,
+        F: FnMut(&mut T) -> Result<O, TokenError>,
+    {
+        tokens.optional_err(|t| {
+            let first = parser(t)?;
+            let mut out = many(t, &mut parser)?;
+            out.insert(0, first);
+            Ok(out)
+        })
+    }
+
+    /// Parses the items after the first of a separated list.
+    fn sep_by_rest<T, O, F, S>(
+        tokens: &mut T,
+        mut out: Vec<O>,
+        parser: &mut F,
+        mut separator: S,
+    ) -> Result<Vec<O>, TokenError>
+    where
+        T: Tokens,
+        F: FnMut(&mut T) -> Result<O, TokenError>,
+        S: FnMut(&mut T) -> bool,
+    {
+        loop {
+            let before_separator = tokens.location();
+            if !separator(tokens) {
+                return Ok(out);
+            }
+            match list_item(tokens, parser)? {
+                Some(item) => out.push(item),
+                // Leave a trailing separator for whatever comes next.
+                None => {
+                    tokens.set_location(before_separator);
+                    return Ok(out);
+                }
+            }
+        }
+    }
+
+    /// Parses zero or more items with `parser` separated by `separator`, like [`Tokens::sep_by`] but with errors
+    /// for items that fail part way through like [`many`]. Consumes nothing on fail.
+    /// ```
+    /// # use advent_lib::parse::yap::{int, line_ending, sep_by, try_all_consuming};
+    /// use yap::{IntoTokens, Tokens};
+    /// let nums = try_all_consuming(&mut "1\n-2\n3\n".into_tokens(), |t| {
+    ///     let nums = sep_by(t, int::<i64>, |t| line_ending(t).is_ok());
+    ///     line_ending(t).ok();
+    ///     nums
+    /// });
+    /// assert_eq!(nums, Ok(vec![1, -2, 3]));
+    /// ```
+    pub fn sep_by<T, O, F, S>(
+        tokens: &mut T,
+        mut parser: F,
+        mut separator: S,
+    ) -> Result<Vec<O>, TokenError>
+    where
+        T: Tokens,
+        F: FnMut(&mut T) -> Result<O, TokenError>,
+        S: FnMut(&mut T) -> bool,
+    {
+        tokens.optional_err(|t| match list_item(t, &mut parser)? {
+            Some(first) => sep_by_rest(t, vec![first], &mut parser, &mut separator),
+            None => Ok(Vec::new()),
+        })
+    }
+
+    /// Like [`sep_by`] but fails with the first item's error if there isn't one.
+    pub fn sep_by1<T, O, F, S>(
+        tokens: &mut T,
+        mut parser: F,
+        mut separator: S,
+    ) -> Result<Vec<O>, TokenError>
+    where
+        T: Tokens,
+        F: FnMut(&mut T) -> Result<O, TokenError>,
+        S: FnMut(&mut T) -> bool,
+    {
+        tokens.optional_err(|t| {
+            let first = parser(t)?;
+            sep_by_rest(t, vec![first], &mut parser, &mut separator)
+        })
+    }
+
+    /// Parses `first` then `second`. Consumes nothing on fail.
+    pub fn pair<T, A, B, FA, FB>(
+        tokens: &mut T,
+        mut first: FA,
+        mut second: FB,
+    ) -> Result<(A, B), TokenError>
+    where
+        T: Tokens,
+        FA: FnMut(&mut T) -> Result<A, TokenError>,
+        FB: FnMut(&mut T) -> Result<B, TokenError>,
+    {
+        tokens.optional_err(|t| Ok((first(t)?, second(t)?)))
+    }
+
+    /// Parses `parser` between the `open` and `close` texts. Consumes nothing on fail.
+    /// ```
+    /// # use advent_lib::parse::yap::{delimited, int, sep_by};
+    /// use yap::{IntoTokens, Tokens};
+    /// let list = delimited(&mut "[1,2]".into_tokens(), "[", |t| sep_by(t, int::<u8>, |t| t.token(',')), "]");
+    /// assert_eq!(list, Ok(vec![1, 2]));
+    /// ```
+    pub fn delimited<T, O, F>(
+        tokens: &mut T,
+        open: &str,
+        mut parser: F,
+        close: &str,
+    ) -> Result<O, TokenError>
+    where
+        T: Tokens<Item = char>,
+        F: FnMut(&mut T) -> Result<O, TokenError>,
+    {
+        tokens.optional_err(|t| {
+            literal(t, open)?;
+            let out = parser(t)?;
+            literal(t, close)?;
+            Ok(out)
+        })
     }
 
     /// Checks that next input is [`None`].
@@ -169,27 +1373,462 @@ pub mod yap {
         }
     }
 
-    /// Error when input wasn't fully consumed.
-    #[derive(Debug, thiserror::Error)]
-    #[error("Input wasn't fully consumed. Failed at offset {0:?} with remainder: {1:?}")]
-    pub struct AllConsuming<E>(usize, E);
-
     /// Attempts to parse all remainder of input until next [`None`]. Consumes nothing on fail.
-    pub fn all_consuming<'a, I, O, E, F>(input: &'a mut I, parser: F) -> Result<O, AllConsuming<E>>
+    ///
+    /// On fail the error points at the first token left over, with lines counted from where parsing started.
+    /// ```
+    /// # use advent_lib::parse::yap::{all_consuming, digit1, line_ending};
+    /// use yap::{IntoTokens, Tokens};
+    /// let err = all_consuming(&mut "12\n3x".into_tokens(), |t| {
+    ///     t.sep_by(|t| digit1::<_, u32>(t).ok(), |t| line_ending(t).is_ok())
+    ///         .collect::<Vec<_>>()
+    /// })
+    /// .unwrap_err();
+    /// assert_eq!((err.line, err.column), (2, 2));
+    /// ```
+    pub fn all_consuming<'a, I, O, F>(input: &'a mut I, parser: F) -> Result<O, super::ParseError>
     where
-        I: Tokens,
+        I: Tokens<Item = char>,
         F: FnOnce(&mut I) -> O + 'a,
-        E: FromIterator<I::Item>,
+    {
+        try_all_consuming(input, |t| Ok(parser(t)))
+    }
+
+    /// Like [`all_consuming`] for a parser that can fail. Its error is located the same way.
+    pub fn try_all_consuming<I, O, F>(input: &mut I, parser: F) -> Result<O, super::ParseError>
+    where
+        I: Tokens<Item = char>,
+        F: FnOnce(&mut I) -> Result<O, TokenError>,
     {
         let before_consuming = input.location();
-        let res = parser(input);
-        // Check nothing comes after
-        if eof(input) {
-            Ok(res)
-        } else {
-            let err = AllConsuming(input.offset(), input.as_iter().collect());
-            input.set_location(before_consuming);
-            Err(err)
+        let err = match parser(input) {
+            // Check nothing comes after
+            Ok(res) if eof(input) => return Ok(res),
+            Ok(_) => TokenError::at(input, "end of input"),
+            Err(e) => e,
+        };
+        // Read the input again from the start to find the line the error is on.
+        input.set_location(before_consuming.clone());
+        let mut source = String::new();
+        let mut offset = None;
+        loop {
+            if offset.is_none() && input.offset() == err.offset {
+                offset = Some(source.len());
+            }
+            match input.next() {
+                Some(c) => source.push(c),
+                None => break,
+            }
         }
+        input.set_location(before_consuming);
+        Err(super::ParseError::new(
+            &source,
+            offset.unwrap_or(source.len()),
+            err.expected,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::nom::{character::complete::digit1, combinator::map_res};
+    use std::error::Error;
+
+    type NomErr<'a> = ::nom::error::Error<&'a str>;
+
+    fn parse_num(input: &Input) -> Result<u32, Box<dyn Error>> {
+        Ok(input.parse_with::<_, NomErr>(map_res(digit1, str::parse))?)
+    }
+
+    #[test]
+    fn input_test() {
+        let input = Input::from("123");
+        assert_eq!(input.parse_with::<_, NomErr>(digit1), Ok("123"));
+        assert_eq!(parse_num(&input).unwrap(), 123);
+        // Anything the parser doesn't consume is an error rather than dropped.
+        let err = parse_num(&Input::from("123abc\n")).unwrap_err();
+        assert!(err.to_string().starts_with("line 1, column 4"));
+        // The error outlives the input it came from.
+        let err = parse_num(&Input::from(String::from("abc"))).unwrap_err();
+        assert!(err.to_string().contains("abc"));
+        assert_eq!(input.lines().count(), 1);
+        assert!(Input::read("does not exist").is_err());
+    }
+
+    #[test]
+    fn parse_error_test() {
+        let source = "ab\r\ncd\r\n\u{e9}fg";
+        let err = ParseError::new(source, source.find('f').unwrap(), "x");
+        assert_eq!((err.line, err.column), (3, 2));
+        assert_eq!(err.snippet, "\u{e9}fg");
+        assert_eq!(err.snippet_offset(), 2);
+        let err = ParseError::new(source, 2, "x");
+        assert_eq!((err.line, err.column, err.snippet.as_str()), (1, 3, "ab"));
+        let err = ParseError::new("ab\n", 3, "x");
+        assert_eq!((err.line, err.column, err.snippet.as_str()), (2, 1, ""));
+    }
+
+    #[test]
+    fn nom_error_test() {
+        use ::nom::{
+            character::complete::{char, line_ending},
+            error::{context, VerboseError},
+            multi::separated_list1,
+            sequence::preceded,
+            IResult,
+        };
+
+        let source = "#1\n#x\n3";
+        let err = Input::from(source)
+            .parse_with::<_, NomErr>(preceded(char('#'), char('2')))
+            .unwrap_err();
+        assert_eq!(
+            (err.line, err.column, err.expected.as_str()),
+            (1, 2, "char")
+        );
+        let err = Input::from(source)
+            .parse_with::<_, VerboseError<_>>(preceded(char('#'), digit1))
+            .unwrap_err();
+        assert_eq!(
+            (err.line, err.column, err.expected.as_str()),
+            (1, 3, "end of file")
+        );
+
+        let verbose: IResult<_, _, VerboseError<_>> = separated_list1(
+            line_ending,
+            context("line", preceded(char('#'), digit1)),
+        )(&source[3..]);
+        let err = ParseError::from_nom(source, verbose.unwrap_err());
+        assert_eq!((err.line, err.column), (2, 2));
+        assert_eq!(err.expected, "digit in line");
+        assert_eq!(err.snippet, "#x");
+
+        // An error from parsing a copy of the source is put at the end instead of panicking.
+        let copy = source.to_owned();
+        let err = ParseError::from_nom(source, char::<_, NomErr>('2')(&copy).unwrap_err());
+        assert_eq!((err.offset, err.line, err.column), (source.len(), 3, 2));
+
+        let incomplete =
+            ::nom::Err::<::nom::error::Error<&str>>::Incomplete(::nom::Needed::Unknown);
+        let err = ParseError::from_nom(source, incomplete);
+        assert_eq!(
+            (err.line, err.column, err.expected.as_str()),
+            (3, 2, "more input")
+        );
+    }
+
+    #[test]
+    fn yap_error_test() {
+        use ::yap::{IntoTokens, Tokens};
+
+        let mut tokens = "ab\ncd".into_tokens();
+        tokens.next();
+        let err = yap::all_consuming(&mut tokens, |t| {
+            t.tokens("b\nc".chars());
+        })
+        .unwrap_err();
+        // Lines are counted from where parsing started.
+        assert_eq!((err.line, err.column, err.snippet.as_str()), (2, 2, "cd"));
+        assert_eq!(err.expected, "end of input");
+        assert_eq!(tokens.offset(), 1, "Nothing is consumed on fail");
+    }
+
+    #[cfg(feature = "nom_locate")]
+    #[test]
+    fn span_test() {
+        use ::nom::{bytes::complete::tag, sequence::preceded};
+
+        let input = Input::from("ab\ncd");
+        let err = preceded(tag::<_, _, ::nom::error::Error<Span>>("ab\n"), digit1)(input.span())
+            .unwrap_err();
+        let err = ParseError::from_nom(&input, err);
+        assert_eq!(
+            (err.line, err.column, err.expected.as_str()),
+            (2, 1, "digit")
+        );
+    }
+
+    #[cfg(feature = "miette")]
+    #[test]
+    fn miette_test() {
+        use miette::Diagnostic;
+
+        let err = ParseError::new("a\nxy\u{e9}z", 4, "digit");
+        let label = err.labels().unwrap().next().unwrap();
+        assert_eq!((label.offset(), label.len()), (2, 2));
+        assert_eq!(label.label(), Some("expected digit"));
+        let mut out = String::new();
+        miette::NarratableReportHandler::new()
+            .render_report(&mut out, &err)
+            .unwrap();
+        // Lines are numbered like the whole input even though only the snippet is kept.
+        assert!(out.contains("snippet line 2: xy\u{e9}z"));
+        assert!(out.contains("label at line 2, column 3"));
+    }
+
+    #[test]
+    fn yap_combinators_test() {
+        use crate::parse::yap::*;
+        use ::yap::{IntoTokens, Tokens};
+
+        let t = &mut "ab  c".into_tokens();
+        assert_eq!(literal(t, "ac").unwrap_err(), TokenError::at(t, "\"ac\""));
+        assert_eq!(t.offset(), 0);
+        assert_eq!(literal(t, "ab"), Ok(()));
+        assert_eq!(space1(t), Ok(2));
+        assert_eq!(space1(t).unwrap_err().expected, "space");
+        assert_eq!(multispace0(&mut " \r\n\tx".into_tokens()), 4);
+
+        assert_eq!(digit1::<_, u32>(&mut "12a".into_tokens()), Ok(12));
+        assert_eq!(signed_digit1::<_, i32>(&mut "-3".into_tokens()), Ok(-3));
+        assert_eq!(
+            alpha1::<_, String>(&mut "ab1".into_tokens()),
+            Ok("ab".into())
+        );
+        let t = &mut "1-2".into_tokens();
+        assert_eq!(
+            signed_digit1::<_, i32>(t).unwrap_err(),
+            TokenError::at(t, "i32")
+        );
+        assert_eq!(t.offset(), 0, "Nothing is consumed on fail");
+        assert!(alpha1::<_, String>(t).is_err());
+        assert_eq!(
+            line_ending(t).unwrap_err(),
+            TokenError::at(t, "line ending")
+        );
+        let t = &mut "\r\n \nx".into_tokens();
+        assert_eq!(section_break(t), Ok(()));
+        let t = &mut "\nx".into_tokens();
+        assert_eq!(section_break(t).unwrap_err().expected, "blank line");
+        assert_eq!(t.offset(), 0);
+
+        assert_eq!(int::<i8>(&mut "-129".into_tokens()).unwrap_err().offset, 3);
+        assert_eq!(int::<i8>(&mut "+127".into_tokens()), Ok(127));
+        assert_eq!(int::<u64>(&mut "0042".into_tokens()), Ok(42));
+        let t = &mut "-x".into_tokens();
+        assert_eq!(
+            int::<i32>(t).unwrap_err(),
+            TokenError {
+                offset: 1,
+                expected: "i32".into()
+            }
+        );
+        assert_eq!(t.offset(), 0, "Nothing is consumed on fail");
+        // Overflow anywhere in the line is an error.
+        assert!(ints_in_line::<u8>(&mut "1 and 256".into_tokens()).is_err());
+        assert_eq!(ints_in_line::<u8>(&mut "".into_tokens()), Ok(vec![]));
+
+        let t = &mut "1,2,".into_tokens();
+        assert_eq!(sep_by1(t, int::<u8>, |t| t.token(',')), Ok(vec![1, 2]));
+        assert_eq!(t.remaining(), ",", "A trailing separator is left");
+        let t = &mut "x".into_tokens();
+        assert_eq!(sep_by(t, int::<u8>, |t| t.token(',')), Ok(vec![]));
+        assert_eq!(
+            sep_by1(t, int::<u8>, |t| t.token(','))
+                .unwrap_err()
+                .expected,
+            "u8"
+        );
+        assert_eq!(many1(t, int::<u8>).unwrap_err().offset, 0);
+        assert_eq!(
+            many1(&mut "1 2".into_tokens(), |t| {
+                space0(t);
+                int::<u8>(t)
+            }),
+            Ok(vec![1, 2])
+        );
+
+        let t = &mut "3x".into_tokens();
+        assert_eq!(
+            pair(t, int::<u8>, |t| literal(t, "y")).unwrap_err().offset,
+            1
+        );
+        assert_eq!(t.offset(), 0);
+
+        let err = try_all_consuming(&mut "[1,2]\n[3,x]".into_tokens(), |t| {
+            sep_by(
+                t,
+                |t| delimited(t, "[", |t| sep_by1(t, int::<u8>, |t| t.token(',')), "]"),
+                |t| line_ending(t).is_ok(),
+            )
+        })
+        .unwrap_err();
+        assert_eq!(
+            (err.line, err.column, err.expected.as_str()),
+            (2, 3, "\"]\"")
+        );
+    }
+
+    #[test]
+    fn sections_test() {
+        let input = "\n a\r\nb\r\n  \r\n\r\nc\n\t\n";
+        let found: Vec<_> = sections(input)
+            .map(|s| (s.text, s.line, s.offset))
+            .collect();
+        assert_eq!(found, [(" a\r\nb", 2, 1), ("c", 6, 14)]);
+        assert_eq!(sections("").count(), 0);
+        assert_eq!(sections("\n \n").count(), 0);
+        assert_eq!(sections("x").next().unwrap().text, "x");
+
+        let second = sections(input).nth(1).unwrap();
+        let err = second.parse_with::<_, NomErr>(digit1).unwrap_err();
+        assert_eq!((err.line, err.column, err.snippet.as_str()), (6, 1, "c"));
+
+        // A single line ending isn't a break.
+        let out = nom::sections(::nom::character::complete::alpha1::<_, ::nom::error::Error<_>>)(
+            "a\n\nb\nc",
+        );
+        assert_eq!(out, Ok(("c", vec!["a", "b"])));
+    }
+
+    #[test]
+    fn blank_line_test() {
+        use ::nom::{
+            character::complete::{line_ending, not_line_ending},
+            combinator::{recognize, verify},
+            multi::separated_list1,
+        };
+        use ::yap::{IntoTokens, Tokens};
+
+        // Only spaces and tabs make a line blank, so the no-break space line starts a section everywhere.
+        let input = "a\n \t\n\u{a0}\nb\r\n\r\nc\n \n";
+        let expected = ["a", "\u{a0}\nb", "c"];
+        let has_text = |line: &str| line.contains(|c| c != ' ' && c != '\t');
+
+        let split: Vec<_> = sections(input).map(|s| s.text.replace('\r', "")).collect();
+        assert_eq!(split, expected);
+        let read: Vec<_> = read_sections(input.as_bytes())
+            .map(|s| s.map(|s| s.text.replace('\r', "")))
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(read, expected);
+
+        let section = recognize(separated_list1(
+            line_ending,
+            verify(not_line_ending, |line: &str| has_text(line)),
+        ));
+        let nommed = nom::sections::<_, _, NomErr, _>(section)(input);
+        assert_eq!(nommed, Ok(("", expected.to_vec())));
+
+        let yapped = yap::try_all_consuming(&mut input.into_tokens(), |t| {
+            yap::sections(t, |t| {
+                let lines: Vec<String> = t
+                    .sep_by(
+                        |t| {
+                            t.optional(|t| {
+                                let line: String =
+                                    t.tokens_while(|&c| c != '\r' && c != '\n').collect();
+                                has_text(&line).then_some(line)
+                            })
+                        },
+                        |t| yap::line_ending(t).is_ok(),
+                    )
+                    .collect();
+                match lines.is_empty() {
+                    true => Err(yap::TokenError::at(t, "line")),
+                    false => Ok(lines.join("\n")),
+                }
+            })
+        });
+        assert_eq!(yapped, Ok(expected.map(String::from).to_vec()));
+    }
+
+    #[test]
+    fn read_test() {
+        let input = "\r\n a\r\nb\r\n  \r\n\r\nc\n\t\n";
+        // Streaming finds the same sections as splitting the whole input.
+        let read: Vec<_> = read_sections(input.as_bytes())
+            .map(|s| s.map(|s| (s.text, s.line, s.offset)))
+            .collect::<Result<_, _>>()
+            .unwrap();
+        let split: Vec<_> = sections(input)
+            .map(|s| (s.text.to_owned(), s.line, s.offset))
+            .collect();
+        assert_eq!(read, split);
+        assert_eq!(read_sections("".as_bytes()).count(), 0);
+        assert_eq!(
+            read_lines("x".as_bytes()).next().unwrap().unwrap().text,
+            "x"
+        );
+        assert_eq!(read_lines("\n\n".as_bytes()).count(), 2);
+
+        let line = read_lines("12\n3x".as_bytes()).nth(1).unwrap().unwrap();
+        let err = line
+            .parse_with::<_, NomErr>(::nom::sequence::pair(digit1, digit1))
+            .unwrap_err();
+        assert_eq!(
+            (err.line, err.column, err.offset, err.snippet.as_str()),
+            (2, 2, 4, "3x")
+        );
+        // Lines after one that isn't UTF-8 keep their place.
+        let bad_utf8: &[u8] = b"a\n\xff\nb\n";
+        let mut lines = read_lines(bad_utf8);
+        assert_eq!(
+            lines.nth(1).unwrap().unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+        let b = lines.next().unwrap().unwrap();
+        assert_eq!((b.text.as_str(), b.line, b.offset), ("b", 3, 4));
+        assert!(lines.next().is_none() && lines.next().is_none());
+        let mut sections = read_sections(b"a\n\n\xff\n\nb".as_slice());
+        assert_eq!(sections.next().unwrap().unwrap().text, "a");
+        assert!(sections.next().unwrap().is_err());
+        assert_eq!(sections.next().unwrap().unwrap().line, 5);
+        assert!(sections.next().is_none() && sections.next().is_none());
+        // The rest of a section with an error in it is skipped rather than split off.
+        let mut sections = read_sections(b"a\n\xff\nb\n\nc".as_slice());
+        assert!(sections.next().unwrap().is_err());
+        let c = sections.next().unwrap().unwrap();
+        assert_eq!((c.text.as_str(), c.line, c.offset), ("c", 5, 7));
+        assert!(sections.next().is_none());
+        assert_eq!(read_sections(b"a\n\xff\nb".as_slice()).count(), 1);
+    }
+
+    #[test]
+    fn scan_test() {
+        use crate::scan;
+
+        let line = String::from("  Sensor at x=-2, y=15:  closest\r");
+        let (x, y): (i32, i32) = scan!(line.trim_start(), "Sensor at x={}, y={}: closest").unwrap();
+        assert_eq!((x, y), (-2, 15));
+        // Whitespace in the pattern needs at least one whitespace character in the input.
+        assert_eq!(scan!("1  ,\t2", "{u8} , {u8}"), Ok((1, 2)));
+        let err = scan!("1,2", "{u8} , {u8}").unwrap_err();
+        assert_eq!((err.column, err.expected.as_str()), (2, "whitespace"));
+        let err = scan!("move3from1to2", "move {u8} from {u8} to {u8}").unwrap_err();
+        assert_eq!((err.column, err.expected.as_str()), (5, "whitespace"));
+        assert_eq!(scan!("12", "{u8} "), Ok(12));
+        // Literals after a capture are looked for past its first character.
+        assert_eq!(scan!("-3-5", "{i32}-{i32}"), Ok((-3, 5)));
+        assert_eq!(scan!("-3 -", "{i32} -"), Ok(-3));
+        // Captures can't be empty, even at the end of the input.
+        let err = scan!("dir", "dir {String}").unwrap_err();
+        assert_eq!(
+            (err.column, err.expected.as_str()),
+            (4, "alloc::string::String")
+        );
+        assert!(scan!("dir ", "dir {String}").is_err());
+        // `{_ws}` is whitespace that can be left out.
+        assert_eq!(scan!("1,2", "{u8}{_ws},{_ws}{u8}"), Ok((1, 2)));
+        assert_eq!(scan!("1 ,\t2", "{u8}{_ws},{_ws}{u8}"), Ok((1, 2)));
+        assert_eq!(scan!("x=1", "{_ws}x = {u8}").unwrap_err().column, 2);
+        let single: Result<char, _> = scan!("[Z]", "[{}]");
+        assert_eq!(single, Ok('Z'));
+        assert_eq!(scan!("1 2  3", "{[u8]}"), Ok(vec![1, 2, 3]));
+        assert_eq!(
+            scan!("a: | b: 1;2 |", "a: {[u8];} | b: {[u8];} |"),
+            Ok((vec![], vec![1, 2]))
+        );
+        assert_eq!(scan!("", ""), Ok(()));
+
+        let err = scan!("move 1 to 2", "move {usize} from {usize} to {usize}").unwrap_err();
+        assert_eq!((err.column, err.expected.as_str()), (8, "\"from\""));
+        let err = scan!("1,2,x", "{[u8],}").unwrap_err();
+        assert_eq!((err.column, err.expected.as_str()), (5, "u8"));
+        let err = scan!("12 left", "{u8} ").unwrap_err();
+        assert_eq!((err.column, err.expected.as_str()), (4, "end of input"));
+        let err = scan!("1-2", "{u8}-{u8}-").unwrap_err();
+        assert_eq!(err.expected, "\"-\"");
     }
 }
@@ -0,0 +1,293 @@
+use crate::{
+    cord::NDCord,
+    dir::{Dir, Rotation},
+};
+use itertools::Itertools;
+use num_traits::Zero;
+use std::{array, ops::Neg};
+
+/// Rotation and/or reflection of the axes, stored as a signed permutation.
+///
+/// Axis `i` of the output is axis `perm[i]` of the input, negated if `negate[i]`.
+/// Transforms are about the origin so they preserve distances and compose without any translation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Transform<const DIM: usize> {
+    perm: [usize; DIM],
+    negate: [bool; DIM],
+}
+
+impl<const DIM: usize> Default for Transform<DIM> {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl<const DIM: usize> Transform<DIM> {
+    pub fn identity() -> Self {
+        Transform {
+            perm: array::from_fn(|i| i),
+            negate: [false; DIM],
+        }
+    }
+
+    /// Quarter turn in the plane of two axes that moves the positive `from` axis onto the positive `to` axis.
+    /// ```
+    /// # use advent_lib::{cord::NDCord, symmetry::Transform};
+    /// // Turn about the z axis taking x to y.
+    /// let turn = Transform::quarter_turn(0, 1);
+    /// assert_eq!(turn.apply(NDCord([1, 0, 5])), NDCord([0, 1, 5]));
+    /// assert_eq!(turn.apply(NDCord([0, 1, 5])), NDCord([-1, 0, 5]));
+    /// ```
+    pub fn quarter_turn(from: usize, to: usize) -> Self {
+        assert_ne!(from, to, "Quarter turn needs two different axes");
+        let mut out = Self::identity();
+        out.perm.swap(from, to);
+        out.negate[from] = true;
+        out
+    }
+
+    /// Mirror across the plane perpendicular to `axis`.
+    pub fn reflect(axis: usize) -> Self {
+        let mut out = Self::identity();
+        out.negate[axis] = true;
+        out
+    }
+
+    /// Transform equivalent to applying `self` and then `next`.
+    pub fn then(&self, next: &Self) -> Self {
+        Transform {
+            perm: array::from_fn(|i| self.perm[next.perm[i]]),
+            negate: array::from_fn(|i| next.negate[i] ^ self.negate[next.perm[i]]),
+        }
+    }
+
+    /// Transform that undoes `self`.
+    pub fn inverse(&self) -> Self {
+        let mut out = Self::identity();
+        for i in 0..DIM {
+            out.perm[self.perm[i]] = i;
+            out.negate[self.perm[i]] = self.negate[i];
+        }
+        out
+    }
+
+    /// If the transform preserves handedness (determinant of `1`). Reflections don't.
+    pub fn is_rotation(&self) -> bool {
+        let inversions = (0..DIM)
+            .tuple_combinations()
+            .filter(|&(i, j)| self.perm[i] > self.perm[j])
+            .count();
+        let negations = self.negate.iter().filter(|&&x| x).count();
+        (inversions + negations) % 2 == 0
+    }
+
+    pub fn apply<T>(&self, cord: NDCord<T, DIM>) -> NDCord<T, DIM>
+    where
+        T: Neg<Output = T> + Clone,
+    {
+        NDCord(array::from_fn(|i| {
+            let x = cord[self.perm[i]].clone();
+            if self.negate[i] {
+                -x
+            } else {
+                x
+            }
+        }))
+    }
+
+    /// Every rotation and reflection of the axes (`2^DIM * DIM!` transforms). Starts with the identity.
+    pub fn all() -> impl Iterator<Item = Self> {
+        (0..DIM).permutations(DIM).flat_map(|perm| {
+            (0..1usize << DIM).map(move |signs| Transform {
+                perm: array::from_fn(|i| perm[i]),
+                negate: array::from_fn(|i| signs & (1 << i) != 0),
+            })
+        })
+    }
+
+    /// Every rotation without reflections. 4 in 2D and 24 in 3D.
+    pub fn rotations() -> impl Iterator<Item = Self> {
+        Self::all().filter(Self::is_rotation)
+    }
+}
+
+impl Transform<2> {
+    /// Turn where directions follow [`Dir::rotate`].
+    pub fn rotation(rotation: &Rotation) -> Self {
+        match rotation {
+            Rotation::Right => Self::quarter_turn(0, 1),
+            Rotation::Left => Self::quarter_turn(1, 0),
+        }
+    }
+
+    pub fn apply_dir(&self, dir: Dir) -> Dir {
+        Dir::from_velocity(self.apply(dir.to_velocity::<i8>()))
+    }
+
+    /// Transform a rectangular grid indexed as `grid[y][x]`. The result is shifted so it starts at `[0][0]`.
+    /// ```
+    /// # use advent_lib::{dir::Rotation, symmetry::Transform};
+    /// let grid = vec![vec![1, 2, 3], vec![4, 5, 6]];
+    /// let turned = Transform::rotation(&Rotation::Right).apply_grid(&grid);
+    /// assert_eq!(turned, vec![vec![4, 1], vec![5, 2], vec![6, 3]]);
+    /// ```
+    pub fn apply_grid<V: Clone>(&self, grid: &[Vec<V>]) -> Vec<Vec<V>> {
+        let height = grid.len();
+        let width = grid.first().map_or(0, Vec::len);
+        let size = self.apply(NDCord([width as isize, height as isize]));
+        // An axis with a negative size has its cells in `(size, 0]` and is shifted back up to start at 0.
+        let offset = NDCord(size.0.map(|x| -(x + 1).min(0)));
+        let (new_width, new_height) = (size[0].unsigned_abs(), size[1].unsigned_abs());
+        let mut out = vec![vec![None; new_width]; new_height];
+        for (y, row) in grid.iter().enumerate() {
+            assert_eq!(row.len(), width, "Grid should be rectangular");
+            for (x, cell) in row.iter().enumerate() {
+                let to = self.apply(NDCord([x as isize, y as isize])) + offset;
+                out[to[1] as usize][to[0] as usize] = Some(cell.clone());
+            }
+        }
+        out.into_iter()
+            .map(|row| {
+                row.into_iter()
+                    .map(|x| x.expect("Every cell is filled"))
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+impl From<Rotation> for Transform<2> {
+    fn from(rotation: Rotation) -> Self {
+        Self::rotation(&rotation)
+    }
+}
+
+/// Representative of a shape's orientations under `group` so that two shapes match exactly when their canonical forms are equal.
+///
+/// The result is the lexicographically smallest sorted list of cells after each transform, translated so the smallest value on each axis is `0`.
+/// ```
+/// # use advent_lib::{cord::NDCord, symmetry::{canonical, Transform}};
+/// let l = [[0, 0], [0, 1], [0, 2], [1, 2]].map(NDCord::<i32, 2>);
+/// let j = [[1, 0], [1, 1], [1, 2], [0, 2]].map(NDCord::<i32, 2>);
+/// // Mirror images only match when reflections are allowed.
+/// assert_ne!(canonical(l, Transform::rotations()), canonical(j, Transform::rotations()));
+/// assert_eq!(canonical(l, Transform::all()), canonical(j, Transform::all()));
+/// ```
+pub fn canonical<T, const DIM: usize>(
+    cells: impl IntoIterator<Item = NDCord<T, DIM>>,
+    group: impl IntoIterator<Item = Transform<DIM>>,
+) -> Vec<NDCord<T, DIM>>
+where
+    T: Neg<Output = T> + Ord + Clone + Zero,
+{
+    let cells: Vec<_> = cells.into_iter().collect();
+    group
+        .into_iter()
+        .map(|transform| {
+            let mut out: Vec<_> = cells.iter().map(|x| transform.apply(x.clone())).collect();
+            if let Some((min, _)) = NDCord::extents_iter(out.iter().cloned()) {
+                for cell in &mut out {
+                    *cell = NDCord(array::from_fn(|i| cell[i].clone() + -min[i].clone()));
+                }
+            }
+            out.sort();
+            out
+        })
+        .min()
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dir::rotate;
+    use enum_iterator::all;
+    use std::collections::HashSet;
+
+    #[test]
+    fn group_size_test() {
+        assert_eq!(Transform::<2>::all().count(), 8);
+        assert_eq!(Transform::<2>::rotations().count(), 4);
+        assert_eq!(Transform::<3>::all().collect::<HashSet<_>>().len(), 48);
+        assert_eq!(Transform::<3>::rotations().count(), 24);
+        assert_eq!(Transform::<3>::all().next(), Some(Transform::identity()));
+    }
+
+    #[test]
+    fn compose_test() {
+        let rotations: HashSet<_> = Transform::<3>::rotations().collect();
+        let cord = NDCord([1, 2, 3]);
+        for a in Transform::<3>::all() {
+            assert_eq!(a.then(&a.inverse()), Transform::identity());
+            assert_eq!(a.inverse().apply(a.apply(cord)), cord);
+            for b in Transform::<3>::all() {
+                let ab = a.then(&b);
+                assert_eq!(ab.apply(cord), b.apply(a.apply(cord)));
+                assert_eq!(ab.is_rotation(), a.is_rotation() == b.is_rotation());
+                if a.is_rotation() && b.is_rotation() {
+                    assert!(rotations.contains(&ab));
+                }
+            }
+        }
+        // Four quarter turns about the same axis is the identity.
+        let turn = Transform::<3>::quarter_turn(2, 0);
+        assert_eq!(
+            turn.then(&turn).then(&turn).then(&turn),
+            Transform::identity()
+        );
+        assert!(!Transform::<3>::reflect(1).is_rotation());
+    }
+
+    #[test]
+    fn dir_test() {
+        for rotation in [Rotation::Left, Rotation::Right] {
+            let transform = Transform::from(rotation);
+            for dir in all::<Dir>() {
+                assert_eq!(transform.apply_dir(dir), dir.rotate(&rotation));
+                let mut velocity = dir.to_velocity::<i32>();
+                rotate(&mut velocity, &rotation);
+                assert_eq!(transform.apply(dir.to_velocity()), velocity);
+            }
+        }
+        assert_eq!(Transform::reflect(0).apply_dir(Dir::Left), Dir::Right);
+        assert_eq!(Transform::reflect(0).apply_dir(Dir::Up), Dir::Up);
+    }
+
+    #[test]
+    fn apply_grid_test() {
+        let grid = vec![vec!['a', 'b', 'c'], vec!['d', 'e', 'f']];
+        assert_eq!(
+            Transform::rotation(&Rotation::Left).apply_grid(&grid),
+            vec![vec!['c', 'f'], vec!['b', 'e'], vec!['a', 'd']]
+        );
+        assert_eq!(
+            Transform::reflect(1).apply_grid(&grid),
+            vec![vec!['d', 'e', 'f'], vec!['a', 'b', 'c']]
+        );
+        let half_turn = Transform::rotation(&Rotation::Right).then(&Rotation::Right.into());
+        assert_eq!(
+            half_turn.apply_grid(&grid),
+            vec![vec!['f', 'e', 'd'], vec!['c', 'b', 'a']]
+        );
+        for transform in Transform::all() {
+            assert_eq!(
+                transform.inverse().apply_grid(&transform.apply_grid(&grid)),
+                grid
+            );
+        }
+    }
+
+    #[test]
+    fn canonical_test() {
+        // Every orientation of a shape gives the same canonical form.
+        let shape = [[0, 0, 0], [1, 0, 0], [1, 1, 0], [1, 1, 2]].map(NDCord::<i64, 3>);
+        let expected = canonical(shape, Transform::rotations());
+        for transform in Transform::rotations() {
+            let moved = shape.map(|x| transform.apply(x) + NDCord([5, -3, 7]));
+            assert_eq!(canonical(moved, Transform::rotations()), expected);
+        }
+        let mirrored = shape.map(|x| Transform::reflect(2).apply(x));
+        assert_ne!(canonical(mirrored, Transform::rotations()), expected);
+        assert!(canonical(Vec::<NDCord<i64, 3>>::new(), Transform::all()).is_empty());
+    }
+}
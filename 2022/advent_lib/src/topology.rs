@@ -0,0 +1,349 @@
+use crate::{cord::NDCord, iters::NDCartesianProduct};
+use num_traits::{cast, One, PrimInt, Zero};
+use std::{
+    array,
+    ops::{Add, Sub},
+};
+
+/// Decides where a cordinate ends up once it moves off the edge of a space.
+///
+/// Neighborhoods and movement resolve through the topology so every position that comes out is a position in the space.
+pub trait Topology<T, const DIM: usize> {
+    /// Map a cordinate that may be outside the space back into it. [`None`] if it has no position in the space.
+    fn resolve(&self, cord: NDCord<T, DIM>) -> Option<NDCord<T, DIM>>;
+
+    /// Move `cord` by `velocity` and resolve the destination.
+    fn step(&self, cord: NDCord<T, DIM>, velocity: NDCord<T, DIM>) -> Option<NDCord<T, DIM>>
+    where
+        T: Add<Output = T>,
+    {
+        self.resolve(cord + velocity)
+    }
+
+    /// [`NDCord::moore_neighborhood`] with each neighbor resolved. Neighbors outside the space are skipped.
+    fn moore_neighborhood<'a>(
+        &'a self,
+        cord: &NDCord<T, DIM>,
+        radius: usize,
+    ) -> impl Iterator<Item = NDCord<T, DIM>> + 'a
+    where
+        T: PrimInt + 'a,
+    {
+        cord.moore_neighborhood(radius)
+            .filter_map(move |x| self.resolve(x))
+    }
+
+    /// [`NDCord::neumann_neighborhood`] with each neighbor resolved. Neighbors outside the space are skipped.
+    fn neumann_neighborhood<'a>(
+        &'a self,
+        cord: &NDCord<T, DIM>,
+        radius: usize,
+    ) -> impl Iterator<Item = NDCord<T, DIM>> + 'a
+    where
+        T: PrimInt + 'a,
+    {
+        cord.neumann_neighborhood(radius)
+            .filter_map(move |x| self.resolve(x))
+    }
+}
+
+/// Every cordinate is in the space and nothing wraps.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Unbounded;
+
+impl<T, const DIM: usize> Topology<T, DIM> for Unbounded {
+    fn resolve(&self, cord: NDCord<T, DIM>) -> Option<NDCord<T, DIM>> {
+        Some(cord)
+    }
+}
+
+/// Space between `min` and `max` (inclusive) where leaving the edge leaves the space.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Bounded<T, const DIM: usize> {
+    pub min: NDCord<T, DIM>,
+    pub max: NDCord<T, DIM>,
+}
+
+impl<T, const DIM: usize> Bounded<T, DIM> {
+    pub fn new(min: NDCord<T, DIM>, max: NDCord<T, DIM>) -> Self {
+        Bounded { min, max }
+    }
+
+    /// Bounds of a grid with `size` cells on each axis starting from zero. Unsigned sizes must be nonzero.
+    pub fn with_size(size: NDCord<T, DIM>) -> Self
+    where
+        T: Zero + One + Sub<Output = T>,
+    {
+        Bounded {
+            min: NDCord(array::from_fn(|_| T::zero())),
+            max: NDCord(size.0.map(|x| x - T::one())),
+        }
+    }
+
+    /// Check if a cordinate is within the bounds.
+    pub fn contains(&self, cord: &NDCord<T, DIM>) -> bool
+    where
+        T: PartialOrd,
+    {
+        (0..DIM).all(|axis| self.min[axis] <= cord[axis] && cord[axis] <= self.max[axis])
+    }
+}
+
+impl<T, const DIM: usize> From<(NDCord<T, DIM>, NDCord<T, DIM>)> for Bounded<T, DIM> {
+    /// From extents as returned by [`NDCord::extents_iter`].
+    fn from((min, max): (NDCord<T, DIM>, NDCord<T, DIM>)) -> Self {
+        Bounded { min, max }
+    }
+}
+
+impl<T: PartialOrd, const DIM: usize> Topology<T, DIM> for Bounded<T, DIM> {
+    fn resolve(&self, cord: NDCord<T, DIM>) -> Option<NDCord<T, DIM>> {
+        self.contains(&cord).then_some(cord)
+    }
+}
+
+/// Space between `min` and `max` (inclusive) where leaving one edge enters from the opposite edge on the same axis.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Toroidal<T, const DIM: usize> {
+    pub min: NDCord<T, DIM>,
+    pub max: NDCord<T, DIM>,
+}
+
+impl<T, const DIM: usize> Toroidal<T, DIM> {
+    pub fn new(min: NDCord<T, DIM>, max: NDCord<T, DIM>) -> Self {
+        Toroidal { min, max }
+    }
+
+    /// Wrap a cordinate on each axis so it lands within the space.
+    /// Wrapping is done in an [`i128`] so unsigned cordinates below `min` wrap instead of overflowing.
+    /// ```
+    /// # use advent_lib::{cord::NDCord, topology::Toroidal};
+    /// let torus = Toroidal::new(NDCord([1, 1]), NDCord([3, 5]));
+    /// assert_eq!(torus.wrap(NDCord([4, 0])), NDCord([1, 5]));
+    /// assert_eq!(torus.wrap(NDCord([-7, 13])), NDCord([2, 3]));
+    /// ```
+    pub fn wrap(&self, cord: NDCord<T, DIM>) -> NDCord<T, DIM>
+    where
+        T: PrimInt,
+    {
+        NDCord(array::from_fn(|axis| {
+            let x = cord[axis].to_i128().expect("Cord fits in i128");
+            cast(self.wrap_wide(axis, x)).expect("Wrapped value is between min and max")
+        }))
+    }
+
+    /// Wrap a single axis of a cordinate that has been widened to an [`i128`].
+    fn wrap_wide(&self, axis: usize, x: i128) -> i128
+    where
+        T: PrimInt,
+    {
+        let min = self.min[axis].to_i128().expect("Cord fits in i128");
+        let max = self.max[axis].to_i128().expect("Cord fits in i128");
+        (x - min).rem_euclid(max - min + 1) + min
+    }
+
+    /// Every cell within `radius` of `cord` on each axis that `keep` accepts the offset of, apart from `cord` itself.
+    /// Offsets are wrapped rather than the neighbors, so neighbors that would be outside `T` still wrap into the space.
+    fn wrapped_neighborhood<'a>(
+        &'a self,
+        cord: &NDCord<T, DIM>,
+        radius: usize,
+        keep: impl Fn(&[i128; DIM]) -> bool + 'a,
+    ) -> impl Iterator<Item = NDCord<T, DIM>> + 'a
+    where
+        T: PrimInt + 'a,
+    {
+        let center = cord.0.map(|x| x.to_i128().expect("Cord fits in i128"));
+        let r = radius as i128;
+        NDCartesianProduct::new(array::from_fn(|_| 0..2 * radius + 1))
+            .map(move |x| x.map(|x| x as i128 - r))
+            .filter(move |offset| offset.iter().any(|&x| x != 0) && keep(offset))
+            .map(move |offset| {
+                NDCord(array::from_fn(|axis| {
+                    cast(self.wrap_wide(axis, center[axis] + offset[axis]))
+                        .expect("Wrapped value is between min and max")
+                }))
+            })
+    }
+}
+
+impl<T, const DIM: usize> From<(NDCord<T, DIM>, NDCord<T, DIM>)> for Toroidal<T, DIM> {
+    /// From extents as returned by [`NDCord::extents_iter`].
+    fn from((min, max): (NDCord<T, DIM>, NDCord<T, DIM>)) -> Self {
+        Toroidal { min, max }
+    }
+}
+
+impl<T, const DIM: usize> Topology<T, DIM> for Toroidal<T, DIM>
+where
+    T: PrimInt,
+{
+    fn resolve(&self, cord: NDCord<T, DIM>) -> Option<NDCord<T, DIM>> {
+        Some(self.wrap(cord))
+    }
+
+    fn moore_neighborhood<'a>(
+        &'a self,
+        cord: &NDCord<T, DIM>,
+        radius: usize,
+    ) -> impl Iterator<Item = NDCord<T, DIM>> + 'a
+    where
+        T: PrimInt + 'a,
+    {
+        self.wrapped_neighborhood(cord, radius, |_| true)
+    }
+
+    fn neumann_neighborhood<'a>(
+        &'a self,
+        cord: &NDCord<T, DIM>,
+        radius: usize,
+    ) -> impl Iterator<Item = NDCord<T, DIM>> + 'a
+    where
+        T: PrimInt + 'a,
+    {
+        self.wrapped_neighborhood(cord, radius, move |offset| {
+            offset.iter().map(|x| x.unsigned_abs()).sum::<u128>() <= radius as u128
+        })
+    }
+}
+
+/// Topology defined by a function for spaces with irregular edges.
+/// ```
+/// # use advent_lib::{cord::NDCord, topology::{CustomWrap, Topology}};
+/// // Cylinder that wraps on the x axis but not on the y axis.
+/// let cylinder = CustomWrap(|x: NDCord<i32, 2>| (0..3).contains(&x[1]).then(|| NDCord([x[0].rem_euclid(4), x[1]])));
+/// assert_eq!(cylinder.resolve(NDCord([-1, 0])), Some(NDCord([3, 0])));
+/// assert_eq!(cylinder.resolve(NDCord([0, 3])), None);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct CustomWrap<F>(pub F);
+
+impl<T, F, const DIM: usize> Topology<T, DIM> for CustomWrap<F>
+where
+    F: Fn(NDCord<T, DIM>) -> Option<NDCord<T, DIM>>,
+{
+    fn resolve(&self, cord: NDCord<T, DIM>) -> Option<NDCord<T, DIM>> {
+        (self.0)(cord)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dir::Dir;
+
+    #[test]
+    fn bounded_test() {
+        let bounds = Bounded::new(NDCord([0, 0]), NDCord([2, 1]));
+        assert_eq!(bounds.resolve(NDCord([2, 1])), Some(NDCord([2, 1])));
+        assert_eq!(bounds.resolve(NDCord([3, 1])), None);
+        assert_eq!(bounds.resolve(NDCord([0, -1])), None);
+        assert_eq!(
+            bounds
+                .moore_neighborhood(&NDCord([0, 0]), 1)
+                .collect::<Vec<_>>(),
+            vec![NDCord([0, 1]), NDCord([1, 0]), NDCord([1, 1])]
+        );
+        assert_eq!(
+            bounds
+                .neumann_neighborhood(&NDCord([2, 1]), 1)
+                .collect::<Vec<_>>(),
+            vec![NDCord([1, 1]), NDCord([2, 0])]
+        );
+        assert_eq!(Dir::Up.step(NDCord([1, 0]), &bounds), None);
+        assert_eq!(
+            Dir::Down.step(NDCord([1, 0]), &bounds),
+            Some(NDCord([1, 1]))
+        );
+    }
+
+    #[test]
+    fn toroidal_test() {
+        let torus = Toroidal::new(NDCord([0, 0]), NDCord([3, 2]));
+        assert_eq!(torus.resolve(NDCord([4, 0])), Some(NDCord([0, 0])));
+        assert_eq!(torus.resolve(NDCord([-1, -1])), Some(NDCord([3, 2])));
+        assert_eq!(torus.resolve(NDCord([9, 7])), Some(NDCord([1, 1])));
+        assert_eq!(Dir::Left.step(NDCord([0, 1]), &torus), Some(NDCord([3, 1])));
+        assert_eq!(Dir::Down.step(NDCord([2, 2]), &torus), Some(NDCord([2, 0])));
+        #[rustfmt::skip]
+        assert_eq!(
+            torus.moore_neighborhood(&NDCord([0, 0]), 1).collect::<Vec<_>>(),
+            vec![
+                NDCord([3, 2]), NDCord([3, 0]), NDCord([3, 1]),
+                NDCord([0, 2]),                 NDCord([0, 1]),
+                NDCord([1, 2]), NDCord([1, 0]), NDCord([1, 1]),
+            ]
+        );
+        assert_eq!(
+            torus
+                .neumann_neighborhood(&NDCord([3, 2]), 1)
+                .collect::<Vec<_>>(),
+            vec![
+                NDCord([2, 2]),
+                NDCord([3, 1]),
+                NDCord([3, 0]),
+                NDCord([0, 2])
+            ]
+        );
+
+        // Unsigned cordinates wrap within bounds that don't start at 0.
+        let torus = Toroidal::new(NDCord([1usize, 1]), NDCord([4, 4]));
+        assert_eq!(
+            torus.step(NDCord([4, 2]), NDCord([1, 0])),
+            Some(NDCord([1, 2]))
+        );
+        assert_eq!(
+            torus.step(NDCord([2, 4]), NDCord([0, 3])),
+            Some(NDCord([2, 3]))
+        );
+        assert_eq!(torus.wrap(NDCord([0, 2])), NDCord([4, 2]));
+        assert_eq!(torus.wrap(NDCord([0, 0])), NDCord([4, 4]));
+
+        // Neighbors below 0 wrap instead of being dropped for not fitting in a usize.
+        let signed = Toroidal::new(NDCord([0, 0]), NDCord([4, 4]));
+        let unsigned = Toroidal::new(NDCord([0usize, 0]), NDCord([4, 4]));
+        let expected: Vec<_> = signed
+            .moore_neighborhood(&NDCord([0, 0]), 1)
+            .map(|x| x.try_cast::<usize>().expect("Wrapped cords are positive"))
+            .collect();
+        assert_eq!(expected.len(), 8);
+        assert_eq!(
+            unsigned
+                .moore_neighborhood(&NDCord([0, 0]), 1)
+                .collect::<Vec<_>>(),
+            expected
+        );
+        assert_eq!(
+            unsigned
+                .neumann_neighborhood(&NDCord([0, 4]), 1)
+                .collect::<Vec<_>>(),
+            vec![
+                NDCord([4, 4]),
+                NDCord([0, 3]),
+                NDCord([0, 0]),
+                NDCord([1, 4])
+            ]
+        );
+    }
+
+    #[test]
+    fn custom_wrap_test() {
+        // Ragged rows where each row wraps within its own length.
+        let rows = [3, 1, 2];
+        let ragged = CustomWrap(|x: NDCord<i32, 2>| {
+            let len = *rows.get(usize::try_from(x[1]).ok()?)?;
+            Some(NDCord([x[0].rem_euclid(len), x[1]]))
+        });
+        assert_eq!(
+            Dir::Right.step(NDCord([2, 0]), &ragged),
+            Some(NDCord([0, 0]))
+        );
+        assert_eq!(
+            Dir::Right.step(NDCord([0, 1]), &ragged),
+            Some(NDCord([0, 1]))
+        );
+        assert_eq!(Dir::Down.step(NDCord([0, 2]), &ragged), None);
+        assert_eq!(ragged.neumann_neighborhood(&NDCord([1, 2]), 1).count(), 3);
+        assert_eq!(Unbounded.resolve(NDCord([-5, 5])), Some(NDCord([-5, 5])));
+    }
+}
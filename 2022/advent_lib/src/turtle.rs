@@ -0,0 +1,205 @@
+use crate::{
+    cord::NDCord,
+    dir::{Dir, Direction, Rotation},
+    topology::Topology,
+};
+use num_traits::{One, Zero};
+use std::ops::{Add, Neg};
+
+/// Walker with a position and a heading that moves by turning and stepping forward.
+///
+/// Where a step ends up is decided by a [`Topology`] or, for movement that can also change the heading (like walking over the edge of a cube), by a step function.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Turtle<T, const DIM: usize, D = Dir> {
+    pub pos: NDCord<T, DIM>,
+    pub heading: D,
+    trail: Option<Vec<NDCord<T, DIM>>>,
+}
+
+impl<T, const DIM: usize, D> Turtle<T, DIM, D>
+where
+    T: Clone,
+    D: Direction<DIM>,
+{
+    pub fn new(pos: NDCord<T, DIM>, heading: D) -> Self {
+        Turtle {
+            pos,
+            heading,
+            trail: None,
+        }
+    }
+
+    /// Start recording every position the turtle visits, beginning with the current one.
+    pub fn with_trail(mut self) -> Self {
+        self.trail = Some(vec![self.pos.clone()]);
+        self
+    }
+
+    /// Positions visited in order if recording with [`Turtle::with_trail`].
+    pub fn trail(&self) -> Option<&[NDCord<T, DIM>]> {
+        self.trail.as_deref()
+    }
+
+    pub fn turn(&mut self, rotation: &Rotation) {
+        self.heading = self.heading.rotate(rotation);
+    }
+
+    /// Step once in the heading. Returns `false` without moving if the topology has no position to move to.
+    pub fn forward<Top>(&mut self, topology: &Top) -> bool
+    where
+        T: Zero + One + Neg<Output = T> + Add<Output = T>,
+        Top: Topology<T, DIM>,
+    {
+        self.forward_with(|pos, heading| {
+            topology
+                .step(pos.clone(), heading.to_velocity())
+                .map(|pos| (pos, heading))
+        })
+    }
+
+    /// Step once to wherever `step` decides from the current position and heading.
+    /// Returns `false` without moving if `step` returns [`None`].
+    pub fn forward_with(
+        &mut self,
+        step: impl FnOnce(&NDCord<T, DIM>, D) -> Option<(NDCord<T, DIM>, D)>,
+    ) -> bool {
+        match step(&self.pos, self.heading) {
+            Some((pos, heading)) => {
+                self.move_to(pos, heading);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Step forward up to `steps` times, stopping in front of the first position that is `blocked` or that the topology has no position for.
+    /// Returns the number of steps taken.
+    ///
+    /// With `steps` of [`usize::MAX`] this walks until blocked, so it won't return on a wrapping topology with nothing blocking the way.
+    /// ```
+    /// # use advent_lib::{cord::NDCord, dir::Dir, topology::Toroidal, turtle::Turtle};
+    /// let torus = Toroidal::new(NDCord([0, 0]), NDCord([4, 0]));
+    /// let mut turtle = Turtle::new(NDCord([3, 0]), Dir::Right);
+    /// assert_eq!(turtle.walk(10, &torus, |x| x[0] == 2), 3);
+    /// assert_eq!(turtle.pos, NDCord([1, 0]));
+    /// ```
+    pub fn walk<Top>(
+        &mut self,
+        steps: usize,
+        topology: &Top,
+        blocked: impl Fn(&NDCord<T, DIM>) -> bool,
+    ) -> usize
+    where
+        T: Zero + One + Neg<Output = T> + Add<Output = T>,
+        Top: Topology<T, DIM>,
+    {
+        self.walk_with(
+            steps,
+            |pos, heading| {
+                topology
+                    .step(pos.clone(), heading.to_velocity())
+                    .map(|pos| (pos, heading))
+            },
+            blocked,
+        )
+    }
+
+    /// [`Turtle::walk`] where each step is decided by `step` like in [`Turtle::forward_with`].
+    pub fn walk_with(
+        &mut self,
+        steps: usize,
+        mut step: impl FnMut(&NDCord<T, DIM>, D) -> Option<(NDCord<T, DIM>, D)>,
+        blocked: impl Fn(&NDCord<T, DIM>) -> bool,
+    ) -> usize {
+        for taken in 0..steps {
+            match step(&self.pos, self.heading) {
+                Some((pos, heading)) if !blocked(&pos) => self.move_to(pos, heading),
+                _ => return taken,
+            }
+        }
+        steps
+    }
+
+    fn move_to(&mut self, pos: NDCord<T, DIM>, heading: D) {
+        if let Some(trail) = &mut self.trail {
+            trail.push(pos.clone());
+        }
+        self.pos = pos;
+        self.heading = heading;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        dir::{Compass, Dir3},
+        topology::{Bounded, Unbounded},
+    };
+
+    #[test]
+    fn forward_turn_test() {
+        let mut turtle = Turtle::new(NDCord([0, 0]), Dir::Right).with_trail();
+        assert!(turtle.forward(&Unbounded));
+        turtle.turn(&Rotation::Right);
+        assert!(turtle.forward(&Unbounded));
+        turtle.turn(&Rotation::Right);
+        assert!(turtle.forward(&Unbounded));
+        assert_eq!(turtle.heading, Dir::Left);
+        assert_eq!(
+            turtle.trail(),
+            Some(
+                &[
+                    NDCord([0, 0]),
+                    NDCord([1, 0]),
+                    NDCord([1, 1]),
+                    NDCord([0, 1])
+                ][..]
+            )
+        );
+
+        let bounds = Bounded::new(NDCord([0, 0]), NDCord([1, 1]));
+        let mut turtle = Turtle::new(NDCord([1, 1]), Compass::SouthWest);
+        assert!(!turtle.forward(&bounds));
+        assert_eq!(turtle.pos, NDCord([1, 1]));
+        turtle.turn(&Rotation::Right);
+        assert!(turtle.forward(&bounds));
+        assert_eq!(turtle.pos, NDCord([0, 1]));
+        assert_eq!(turtle.trail(), None);
+    }
+
+    #[test]
+    fn walk_test() {
+        let bounds = Bounded::new(NDCord([0, 0, 0]), NDCord([0, 0, 9]));
+        let mut turtle = Turtle::new(NDCord([0, 0, 0]), Dir3::Forward).with_trail();
+        // Stops at the edge of the bounds.
+        assert_eq!(turtle.walk(usize::MAX, &bounds, |_| false), 9);
+        assert_eq!(turtle.trail().map(<[_]>::len), Some(10));
+        turtle.turn(&Rotation::Left);
+        turtle.turn(&Rotation::Left);
+        assert_eq!(turtle.heading, Dir3::Forward);
+        turtle.heading = turtle.heading.opposite();
+        // Stops in front of a blocked position.
+        assert_eq!(turtle.walk(100, &bounds, |x| x[2] == 3), 5);
+        assert_eq!(turtle.pos, NDCord([0, 0, 4]));
+        assert_eq!(turtle.walk(2, &bounds, |_| false), 2);
+        assert_eq!(turtle.pos, NDCord([0, 0, 2]));
+    }
+
+    #[test]
+    fn walk_with_test() {
+        // Turn around instead of leaving the ends of a corridor.
+        let mut turtle = Turtle::new(NDCord([0, 0]), Dir::Right);
+        let bounce = |pos: &NDCord<i32, 2>, heading: Dir| {
+            let next = *pos + heading.to_velocity();
+            Some(if (0..3).contains(&next[0]) {
+                (next, heading)
+            } else {
+                (*pos, heading.opposite())
+            })
+        };
+        assert_eq!(turtle.walk_with(6, bounce, |_| false), 6);
+        assert_eq!((turtle.pos, turtle.heading), (NDCord([0, 0]), Dir::Right));
+        assert!(!turtle.forward_with(|_, _| None));
+    }
+}
@@ -0,0 +1,265 @@
+//! Procedural macros re-exported by `advent_lib`.
+use proc_macro::TokenStream;
+use quote::{format_ident, quote};
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_macro_input, Expr, LitStr, Token, Type,
+};
+
+/// Part of a `scan!` pattern.
+#[derive(Clone, Debug, PartialEq)]
+enum Piece {
+    /// Text that must match exactly.
+    Literal(String),
+    /// At least one whitespace character, or the start or end of the input.
+    Whitespace,
+    /// Any amount of whitespace, including none. Written `{_ws}`.
+    OptionalWhitespace,
+    /// A value parsed with `FromStr`. Inferred from how the output is used if there's no type.
+    Capture(Option<String>),
+    /// Values parsed with `FromStr` between separators. Whitespace separated if the separator is empty.
+    List {
+        ty: Option<String>,
+        separator: String,
+    },
+}
+
+/// Split a pattern into pieces, checking that every capture can be told apart from what follows it.
+fn parse_pattern(pattern: &str) -> Result<Vec<Piece>, String> {
+    let mut pieces = Vec::new();
+    let mut literal = String::new();
+    let mut chars = pattern.chars().peekable();
+    let flush = |literal: &mut String, pieces: &mut Vec<Piece>| {
+        if !literal.is_empty() {
+            pieces.push(Piece::Literal(std::mem::take(literal)));
+        }
+    };
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '}' => return Err("unmatched `}` in pattern, use `}}` for a literal `}`".to_owned()),
+            '{' => {
+                let mut capture = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some('{') => return Err("`{` inside a capture".to_owned()),
+                        Some(c) => capture.push(c),
+                        None => {
+                            return Err(
+                                "unclosed `{` in pattern, use `{{` for a literal `{`".to_owned()
+                            )
+                        }
+                    }
+                }
+                flush(&mut literal, &mut pieces);
+                if capture.trim() == "_ws" {
+                    pieces.push(Piece::OptionalWhitespace);
+                    continue;
+                }
+                // Optional whitespace might not be there so it can't tell captures apart.
+                let before = pieces
+                    .iter()
+                    .rev()
+                    .find(|x| !matches!(x, Piece::OptionalWhitespace));
+                if matches!(before, Some(Piece::Capture(_) | Piece::List { .. })) {
+                    return Err("captures must be separated by text or whitespace".to_owned());
+                }
+                let ty = |ty: &str| {
+                    let ty = ty.trim();
+                    (!ty.is_empty() && ty != "_").then(|| ty.to_owned())
+                };
+                pieces.push(match capture.trim_start().strip_prefix('[') {
+                    Some(list) => {
+                        let (elem, separator) = list
+                            .split_once(']')
+                            .ok_or("list capture is missing its closing `]`")?;
+                        Piece::List {
+                            ty: ty(elem),
+                            separator: separator.trim().to_owned(),
+                        }
+                    }
+                    None => Piece::Capture(ty(&capture)),
+                });
+            }
+            c if c.is_whitespace() => {
+                while chars.next_if(|c| c.is_whitespace()).is_some() {}
+                flush(&mut literal, &mut pieces);
+                pieces.push(Piece::Whitespace);
+            }
+            c => literal.push(c),
+        }
+    }
+    flush(&mut literal, &mut pieces);
+    Ok(pieces)
+}
+
+struct ScanInput {
+    input: Expr,
+    pattern: LitStr,
+}
+
+impl Parse for ScanInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let out = ScanInput {
+            input: input.parse()?,
+            pattern: {
+                input.parse::<Token![,]>()?;
+                input.parse()?
+            },
+        };
+        input.parse::<Option<Token![,]>>()?;
+        Ok(out)
+    }
+}
+
+/// See `advent_lib::scan`.
+#[proc_macro]
+pub fn scan(tokens: TokenStream) -> TokenStream {
+    let ScanInput { input, pattern } = parse_macro_input!(tokens as ScanInput);
+    let pieces = match parse_pattern(&pattern.value()) {
+        Ok(x) => x,
+        Err(e) => return syn::Error::new(pattern.span(), e).to_compile_error().into(),
+    };
+    let parse_ty = |ty: &Option<String>| match ty {
+        Some(ty) => syn::parse_str::<Type>(ty)
+            .map(|ty| quote!(#ty))
+            .map_err(|e| {
+                syn::Error::new(pattern.span(), format!("invalid capture type `{ty}`: {e}"))
+            }),
+        None => Ok(quote!(_)),
+    };
+
+    let mut steps = Vec::new();
+    let mut outputs = Vec::new();
+    for (i, piece) in pieces.iter().enumerate() {
+        // A capture ends before the next text, or the next whitespace if that comes first.
+        let end = || match &pieces[i + 1..] {
+            [Piece::Literal(text), ..] => quote!(::advent_lib::parse::scan::End::Text(#text)),
+            [Piece::Whitespace | Piece::OptionalWhitespace, Piece::Literal(text), ..] => {
+                quote!(::advent_lib::parse::scan::End::Whitespace(::core::option::Option::Some(#text)))
+            }
+            [Piece::Whitespace | Piece::OptionalWhitespace, ..] => {
+                quote!(::advent_lib::parse::scan::End::Whitespace(
+                    ::core::option::Option::None
+                ))
+            }
+            _ => quote!(::advent_lib::parse::scan::End::Input),
+        };
+        // Lists can hold whitespace so they run up to the next text.
+        let list_end = || match pieces[i + 1..]
+            .iter()
+            .find(|x| !matches!(x, Piece::Whitespace | Piece::OptionalWhitespace))
+        {
+            Some(Piece::Literal(text)) => quote!(::advent_lib::parse::scan::End::Text(#text)),
+            _ => quote!(::advent_lib::parse::scan::End::Input),
+        };
+        match piece {
+            Piece::Literal(text) => steps.push(quote!(scanner.literal(#text)?;)),
+            Piece::Whitespace => steps.push(quote!(scanner.whitespace()?;)),
+            Piece::OptionalWhitespace => steps.push(quote!(scanner.optional_whitespace();)),
+            Piece::Capture(ty) => {
+                let name = format_ident!("capture_{}", outputs.len());
+                let ty = match parse_ty(ty) {
+                    Ok(x) => x,
+                    Err(e) => return e.to_compile_error().into(),
+                };
+                let end = end();
+                steps.push(quote!(let #name: #ty = scanner.capture(#end)?;));
+                outputs.push(name);
+            }
+            Piece::List { ty, separator } => {
+                let name = format_ident!("capture_{}", outputs.len());
+                let ty = match parse_ty(ty) {
+                    Ok(x) => x,
+                    Err(e) => return e.to_compile_error().into(),
+                };
+                let end = list_end();
+                steps.push(
+                    quote!(let #name: ::std::vec::Vec<#ty> = scanner.list(#separator, #end)?;),
+                );
+                outputs.push(name);
+            }
+        }
+    }
+    let output = match outputs.as_slice() {
+        [single] => quote!(#single),
+        _ => quote!((#(#outputs),*)),
+    };
+    quote!(
+        // `match` keeps temporaries in the input alive until scanning is done.
+        match ::core::convert::AsRef::<str>::as_ref(&#input) {
+            source => {
+                let mut scanner = ::advent_lib::parse::scan::Scanner::new(source);
+                (|| -> ::core::result::Result<_, ::advent_lib::parse::ParseError> {
+                    #(#steps)*
+                    scanner.finish()?;
+                    ::core::result::Result::Ok(#output)
+                })()
+            }
+        }
+    )
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_pattern_test() {
+        use Piece::*;
+        assert_eq!(
+            parse_pattern("move {} from {usize} to {u8}"),
+            Ok(vec![
+                Literal("move".to_owned()),
+                Whitespace,
+                Capture(None),
+                Whitespace,
+                Literal("from".to_owned()),
+                Whitespace,
+                Capture(Some("usize".to_owned())),
+                Whitespace,
+                Literal("to".to_owned()),
+                Whitespace,
+                Capture(Some("u8".to_owned())),
+            ])
+        );
+        assert_eq!(
+            parse_pattern("{{{_}}}: {[u64],}{[]}"),
+            Err("captures must be separated by text or whitespace".to_owned())
+        );
+        assert_eq!(
+            parse_pattern("{{{_}}}: {[u64] , }"),
+            Ok(vec![
+                Literal("{".to_owned()),
+                Capture(None),
+                Literal("}:".to_owned()),
+                Whitespace,
+                List {
+                    ty: Some("u64".to_owned()),
+                    separator: ",".to_owned()
+                },
+            ])
+        );
+        assert_eq!(
+            parse_pattern("{u8}{ _ws },"),
+            Ok(vec![
+                Capture(Some("u8".to_owned())),
+                OptionalWhitespace,
+                Literal(",".to_owned()),
+            ])
+        );
+        assert!(parse_pattern("{}{_ws}{}").is_err());
+        assert!(parse_pattern("{}}").is_err());
+        assert!(parse_pattern("{").is_err());
+        assert!(parse_pattern("{[u8}").is_err());
+    }
+}